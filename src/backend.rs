@@ -0,0 +1,226 @@
+//! A trait over the core object operations, so downstream code can swap `StorageClient` for a
+//! fake in its own tests
+//!
+//! ```rust
+//! use supabase_storage_rs::prelude::*;
+//!
+//! async fn save_avatar(storage: &dyn StorageBackend, user: &str, image: Vec<u8>) -> Result<(), Error> {
+//!     storage
+//!         .upload_file("avatars", image, &format!("{user}.png"), None)
+//!         .await?;
+//!     Ok(())
+//! }
+//! ```
+
+use futures_util::future::BoxFuture;
+
+use crate::{
+    errors::Error,
+    models::{
+        BucketResponse, DownloadOptions, FileObject, FileOptions, FileSearchOptions,
+        ObjectResponse, Outcome, StorageClient,
+    },
+};
+
+/// The core object operations of `StorageClient`
+///
+/// Each method mirrors the `StorageClient` method of the same name. They return boxed futures
+/// so the trait stays object safe and can be used as `&dyn StorageBackend`.
+pub trait StorageBackend: Send + Sync {
+    /// See `StorageClient::upload_file`
+    fn upload_file<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        data: Vec<u8>,
+        path: &'a str,
+        options: Option<FileOptions<'a>>,
+    ) -> BoxFuture<'a, Result<ObjectResponse, Error>>;
+
+    /// See `StorageClient::update_file`
+    fn update_file<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        data: Vec<u8>,
+        path: &'a str,
+        options: Option<FileOptions<'a>>,
+    ) -> BoxFuture<'a, Result<ObjectResponse, Error>>;
+
+    /// See `StorageClient::download_file`
+    fn download_file<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: &'a str,
+        options: Option<DownloadOptions<'a>>,
+    ) -> BoxFuture<'a, Result<Vec<u8>, Error>>;
+
+    /// See `StorageClient::list_files`
+    fn list_files<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: Option<&'a str>,
+        options: Option<FileSearchOptions<'a>>,
+    ) -> BoxFuture<'a, Result<Vec<FileObject>, Error>>;
+
+    /// See `StorageClient::exists`
+    fn exists<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: &'a str,
+    ) -> BoxFuture<'a, Result<bool, Error>>;
+
+    /// See `StorageClient::delete_file`
+    fn delete_file<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: &'a str,
+    ) -> BoxFuture<'a, Result<BucketResponse, Error>>;
+
+    /// See `StorageClient::delete_files`
+    fn delete_files<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        paths: Vec<&'a str>,
+    ) -> BoxFuture<'a, Result<Vec<FileObject>, Error>>;
+
+    /// See `StorageClient::copy_file`
+    fn copy_file<'a>(
+        &'a self,
+        from_bucket: &'a str,
+        to_bucket: Option<&'a str>,
+        from_path: &'a str,
+        to_path: Option<&'a str>,
+        copy_metadata: bool,
+    ) -> BoxFuture<'a, Result<String, Error>>;
+
+    /// See `StorageClient::move_file`
+    fn move_file<'a>(
+        &'a self,
+        from_bucket: &'a str,
+        to_bucket: Option<&'a str>,
+        from_path: &'a str,
+        to_path: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Outcome, Error>>;
+
+    /// See `StorageClient::create_signed_url`
+    fn create_signed_url<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: &'a str,
+        expires_in: u64,
+        options: Option<DownloadOptions<'a>>,
+    ) -> BoxFuture<'a, Result<String, Error>>;
+}
+
+impl StorageBackend for StorageClient {
+    fn upload_file<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        data: Vec<u8>,
+        path: &'a str,
+        options: Option<FileOptions<'a>>,
+    ) -> BoxFuture<'a, Result<ObjectResponse, Error>> {
+        Box::pin(StorageClient::upload_file(
+            self, bucket_id, data, path, options,
+        ))
+    }
+
+    fn update_file<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        data: Vec<u8>,
+        path: &'a str,
+        options: Option<FileOptions<'a>>,
+    ) -> BoxFuture<'a, Result<ObjectResponse, Error>> {
+        Box::pin(StorageClient::update_file(
+            self, bucket_id, data, path, options,
+        ))
+    }
+
+    fn download_file<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: &'a str,
+        options: Option<DownloadOptions<'a>>,
+    ) -> BoxFuture<'a, Result<Vec<u8>, Error>> {
+        Box::pin(StorageClient::download_file(self, bucket_id, path, options))
+    }
+
+    fn list_files<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: Option<&'a str>,
+        options: Option<FileSearchOptions<'a>>,
+    ) -> BoxFuture<'a, Result<Vec<FileObject>, Error>> {
+        Box::pin(StorageClient::list_files(self, bucket_id, path, options))
+    }
+
+    fn exists<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: &'a str,
+    ) -> BoxFuture<'a, Result<bool, Error>> {
+        Box::pin(StorageClient::exists(self, bucket_id, path))
+    }
+
+    fn delete_file<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: &'a str,
+    ) -> BoxFuture<'a, Result<BucketResponse, Error>> {
+        Box::pin(StorageClient::delete_file(self, bucket_id, path))
+    }
+
+    fn delete_files<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        paths: Vec<&'a str>,
+    ) -> BoxFuture<'a, Result<Vec<FileObject>, Error>> {
+        Box::pin(StorageClient::delete_files(self, bucket_id, paths))
+    }
+
+    fn copy_file<'a>(
+        &'a self,
+        from_bucket: &'a str,
+        to_bucket: Option<&'a str>,
+        from_path: &'a str,
+        to_path: Option<&'a str>,
+        copy_metadata: bool,
+    ) -> BoxFuture<'a, Result<String, Error>> {
+        Box::pin(StorageClient::copy_file(
+            self,
+            from_bucket,
+            to_bucket,
+            from_path,
+            to_path,
+            copy_metadata,
+        ))
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        from_bucket: &'a str,
+        to_bucket: Option<&'a str>,
+        from_path: &'a str,
+        to_path: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Outcome, Error>> {
+        Box::pin(StorageClient::move_file(
+            self,
+            from_bucket,
+            to_bucket,
+            from_path,
+            to_path,
+        ))
+    }
+
+    fn create_signed_url<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: &'a str,
+        expires_in: u64,
+        options: Option<DownloadOptions<'a>>,
+    ) -> BoxFuture<'a, Result<String, Error>> {
+        Box::pin(StorageClient::create_signed_url(
+            self, bucket_id, path, expires_in, options,
+        ))
+    }
+}
@@ -1,32 +1,71 @@
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bytes::Bytes;
+use futures_util::{future::BoxFuture, stream, Stream, StreamExt, TryStream, TryStreamExt};
 use reqwest::{
-    header::{HeaderMap, HeaderValue, IntoHeaderName, AUTHORIZATION, CACHE_CONTROL, CONTENT_TYPE},
-    Url,
+    header::{
+        HeaderMap, HeaderValue, IntoHeaderName, ACCEPT, ACCEPT_RANGES, AUTHORIZATION,
+        CACHE_CONTROL, CONTENT_DISPOSITION, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, ETAG,
+        IF_NONE_MATCH, LAST_MODIFIED, USER_AGENT,
+    },
+    Body, Method, Proxy, RequestBuilder, Response, StatusCode, Url,
 };
+use serde::de::DeserializeOwned;
+#[cfg(feature = "cancellation")]
+use tokio_util::sync::CancellationToken;
 
 use crate::{
     errors::Error,
     models::{
-        Bucket, BucketResponse, Buckets, CopyFilePayload, CopyFileResponse, CreateBucket,
-        CreateBucketResponse, CreateMultipleSignedUrlsPayload, CreateSignedUrlPayload,
-        DownloadOptions, FileObject, FileOptions, FileSearchOptions, ListFilesPayload, MimeType,
-        MoveFilePayload, ObjectResponse, SignedUploadUrlResponse, SignedUrlResponse, StorageClient,
-        UpdateBucket, UploadToSignedUrlResponse, HEADER_API_KEY, STORAGE_V1,
+        self, Bucket, BucketOptions, BucketResponse, Buckets, Column, ConditionalDownload,
+        CopyFilePayload, CopyFileResponse, CreateBucket, CreateBucketResponse,
+        CreateMultipleSignedUrlsPayload, CreateSignedUrlPayload, DeleteFilesPayload,
+        DownloadOptions, DownloadResponse, FileObject, FileOptions, FilePage, FileSearchOptions,
+        FileTree, ListFilesPayload, MimeType, MoveFilePayload, MultipleSignedUrlResponse,
+        ObjectMetadataPayload, ObjectResponse, Outcome, SignedUploadUrlResponse, SignedUrlEntry,
+        SignedUrlResponse, SignedUrlWithToken, SortBy, StorageClient, StorageClientBuilder,
+        StorageErrorBody, UpdateBucket, UpdateBucketOptions, UploadToSignedUrlResponse,
+        BODY_SNIPPET_LEN, DEFAULT_MAX_CONCURRENCY, DEFAULT_MAX_SIGNED_URL_EXPIRY,
+        DEFAULT_SUCCESS_MESSAGE, DEFAULT_USER_AGENT, DELETE_BATCH_SIZE, EMPTY_FOLDER_PLACEHOLDER,
+        HEADER_API_KEY, LIST_PAGE_SIZE, MAX_FOLDER_DEPTH, SIGN_BATCH_SIZE, STORAGE_V1,
+        UPLOAD_CHUNK_SIZE,
     },
 };
 
 impl StorageClient {
     /// Create a new StorageClient from a project_url and api_key
     /// You can find your project url and keys at `https://supabase.com/dashboard/project/YOUR_PROJECT_ID/settings/api`
+    ///
+    /// A `project_url` without a scheme, like `<project ref>.supabase.co`, is assumed to be
+    /// `https://`. This applies to every constructor and the builder.
     /// # Example
     /// ```
     /// let client = StorageClient::new(project_url, api_key, jwt_secret).unwrap();
     /// ```
     pub fn new(project_url: String, api_key: String) -> Self {
         Self {
-            client: reqwest::Client::new(),
-            project_url,
+            client: default_http_client(),
+            project_url: normalize_project_url(project_url),
             api_key,
             headers: HeaderMap::new(),
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            query: Vec::new(),
+            max_retries: 0,
+            default_cache_control: None,
+            send_api_key_header: true,
+            max_signed_url_expiry: DEFAULT_MAX_SIGNED_URL_EXPIRY,
+            bucket_cache: Default::default(),
+            request_middleware: None,
+            #[cfg(feature = "middleware")]
+            middleware_client: None,
         }
     }
 
@@ -43,12 +82,64 @@ impl StorageClient {
         let project_url = std::env::var("SUPABASE_URL")?;
         let api_key = std::env::var("SUPABASE_API_KEY")?;
 
-        Ok(StorageClient {
-            client: reqwest::Client::new(),
-            project_url,
-            api_key,
+        StorageClient::builder(project_url, api_key).build()
+    }
+
+    /// Create a new StorageClient that sends its requests through a `reqwest-middleware` client,
+    /// e.g. one with retry or tracing middleware
+    ///
+    /// Requests are built as usual and only executed by `client`, so `with_request_middleware`
+    /// still applies first.
+    ///
+    /// # Example
+    /// ```
+    /// let retry_policy = ExponentialBackoff::builder().build_with_max_retries(3);
+    /// let client = ClientBuilder::new(reqwest::Client::new())
+    ///     .with(RetryTransientMiddleware::new_with_policy(retry_policy))
+    ///     .build();
+    ///
+    /// let storage = StorageClient::new_with_middleware(project_url, api_key, client);
+    /// ```
+    #[cfg(feature = "middleware")]
+    pub fn new_with_middleware(
+        project_url: String,
+        api_key: String,
+        client: reqwest_middleware::ClientWithMiddleware,
+    ) -> Self {
+        Self {
+            middleware_client: Some(client),
+            ..Self::new(project_url, api_key)
+        }
+    }
+
+    /// Start building a StorageClient from a project_url and api_key
+    ///
+    /// # Example
+    /// ```
+    /// let client = StorageClient::builder(project_url, api_key)
+    ///     .header("x-client-info", "my-app")
+    ///     .max_concurrency(4)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder(
+        project_url: impl Into<String>,
+        api_key: impl Into<String>,
+    ) -> StorageClientBuilder {
+        StorageClientBuilder {
+            project_url: project_url.into(),
+            api_key: api_key.into(),
+            client: None,
             headers: HeaderMap::new(),
-        })
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            query: Vec::new(),
+            max_retries: 0,
+            proxy: None,
+            default_cache_control: None,
+            send_api_key_header: true,
+            max_signed_url_expiry: DEFAULT_MAX_SIGNED_URL_EXPIRY,
+            error: None,
+        }
     }
 
     pub fn insert_header(
@@ -63,6 +154,235 @@ impl StorageClient {
         self
     }
 
+    /// Set the most requests a batch helper, like `download_files` or `delete_folder`, sends at
+    /// once
+    ///
+    /// Defaults to `DEFAULT_MAX_CONCURRENCY`. Values below 1 are treated as 1.
+    ///
+    /// # Example
+    /// ```
+    /// let client = StorageClient::new(project_url, api_key).with_max_concurrency(4);
+    /// ```
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    /// Append a query parameter to every request the client sends
+    ///
+    /// This is an escape hatch for API parameters the client doesn't model yet. URLs that are
+    /// built without a request, like `get_public_url`, are unaffected.
+    ///
+    /// # Example
+    /// ```
+    /// let client = StorageClient::new(project_url, api_key).with_query("version", "2");
+    /// ```
+    pub fn with_query(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.query.push((name.into(), value.into()));
+        self
+    }
+
+    /// Retry idempotent uploads up to `max_retries` times when they time out, fail to connect,
+    /// or are answered with `429`, `502`, `503` or `504`
+    ///
+    /// Only uploads that replace the object are retried: `update_file`, and `upload_file` with
+    /// `upsert` set. Retrying a plain `upload_file` (a create `POST`) after an ambiguous failure
+    /// could repeat a create that already succeeded, so it is always sent once. Streamed bodies
+    /// can't be replayed and are never retried either. Retries are sent immediately.
+    ///
+    /// Defaults to `0`, i.e. no retries.
+    ///
+    /// # Example
+    /// ```
+    /// let client = StorageClient::new(project_url, api_key).with_max_retries(3);
+    /// ```
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Whether to send the `apikey` header alongside the `Authorization` bearer
+    ///
+    /// The bucket methods send it by default. Turn it off for gateways that only accept the
+    /// bearer token. An `apikey` header added with `insert_header` is still sent.
+    ///
+    /// # Example
+    /// ```
+    /// let client = StorageClient::new(project_url, api_key).with_send_api_key_header(false);
+    /// ```
+    pub fn with_send_api_key_header(mut self, send_api_key_header: bool) -> Self {
+        self.send_api_key_header = send_api_key_header;
+        self
+    }
+
+    /// Set the longest expiry, in seconds, accepted when creating signed URLs
+    ///
+    /// Signed URL methods fail with `Error::InvalidArgument` for a longer expiry, rather than
+    /// sending a request the server may reject or a URL that stays valid for longer than
+    /// intended. Defaults to `DEFAULT_MAX_SIGNED_URL_EXPIRY`, one year.
+    ///
+    /// # Example
+    /// ```
+    /// let client = StorageClient::new(project_url, api_key).with_max_signed_url_expiry(3600);
+    /// ```
+    pub fn with_max_signed_url_expiry(mut self, max_signed_url_expiry: u64) -> Self {
+        self.max_signed_url_expiry = max_signed_url_expiry;
+        self
+    }
+
+    /// Forget the allowed mime types cached for `FileOptions::validate_mime_type`, for one bucket
+    /// or, with `None`, for every bucket
+    ///
+    /// `update_bucket` and `delete_bucket` already do this for their bucket, so this is only
+    /// needed when a bucket is changed elsewhere, e.g. in the dashboard.
+    ///
+    /// # Example
+    /// ```
+    /// client.invalidate_allowed_mime_types(Some("avatars"));
+    /// ```
+    pub fn invalidate_allowed_mime_types(&self, bucket_id: Option<&str>) {
+        let mut cache = self.bucket_cache.lock().unwrap();
+        match bucket_id {
+            Some(bucket_id) => {
+                cache.remove(bucket_id);
+            }
+            None => cache.clear(),
+        }
+    }
+
+    /// Send `cache_control` as the Cache-Control header of every upload that doesn't set
+    /// `FileOptions::cache_control` or `FileOptions::cache_control_header`
+    ///
+    /// # Example
+    /// ```
+    /// let client = StorageClient::new(project_url, api_key)
+    ///     .with_default_cache_control("max-age=31536000, immutable")
+    ///     .unwrap();
+    ///
+    /// // Or from a `Duration`
+    /// let client = StorageClient::new(project_url, api_key)
+    ///     .with_default_cache_control(&format!("max-age={}", max_age.as_secs()))
+    ///     .unwrap();
+    /// ```
+    pub fn with_default_cache_control(mut self, cache_control: &str) -> Result<Self, Error> {
+        self.default_cache_control = Some(HeaderValue::from_str(cache_control)?);
+        Ok(self)
+    }
+
+    /// Send `user_agent` as the `User-Agent` header of every request, e.g. for proxies that
+    /// filter on it
+    ///
+    /// Clients created by this crate send `DEFAULT_USER_AGENT` otherwise. This also overrides the
+    /// user agent of a client passed to `StorageClientBuilder::http_client`.
+    ///
+    /// # Example
+    /// ```
+    /// let client = StorageClient::new(project_url, api_key)
+    ///     .with_user_agent("my-app/1.0")
+    ///     .unwrap();
+    /// ```
+    pub fn with_user_agent(mut self, user_agent: &str) -> Result<Self, Error> {
+        self.headers
+            .insert(USER_AGENT, HeaderValue::from_str(user_agent)?);
+        Ok(self)
+    }
+
+    /// Pass every request through `middleware` just before it's sent, e.g. to add a signed header
+    /// or change the timeout
+    ///
+    /// Replaces any middleware set before.
+    ///
+    /// # Example
+    /// ```
+    /// let client = StorageClient::new(project_url, api_key)
+    ///     .with_request_middleware(|request| request.timeout(Duration::from_secs(5)));
+    /// ```
+    pub fn with_request_middleware(
+        mut self,
+        middleware: impl Fn(RequestBuilder) -> RequestBuilder + Send + Sync + 'static,
+    ) -> Self {
+        self.request_middleware = Some(Arc::new(middleware));
+        self
+    }
+
+    /// Route every request through the proxy at `proxy_url`, e.g. `http://proxy:8080`
+    ///
+    /// This replaces the client's `reqwest::Client`, so use `StorageClient::builder` with
+    /// `http_client` instead when the client needs other settings too.
+    ///
+    /// # Example
+    /// ```
+    /// let client = StorageClient::new(project_url, api_key)
+    ///     .with_proxy("http://proxy:8080")
+    ///     .unwrap();
+    /// ```
+    pub fn with_proxy(mut self, proxy_url: &str) -> Result<Self, Error> {
+        self.client = http_client_builder()
+            .proxy(parse_proxy(proxy_url)?)
+            .build()?;
+        Ok(self)
+    }
+
+    /// Replace the bearer token sent in the `Authorization` header of subsequent requests
+    ///
+    /// Use this to refresh an expiring JWT in place. Clones made before the call keep the old
+    /// token. The `apikey` header is still sent with the `api_key` the client was created with.
+    ///
+    /// # Example
+    /// ```
+    /// client.set_auth_token(&refreshed_jwt).unwrap();
+    /// ```
+    pub fn set_auth_token(&mut self, token: &str) -> Result<(), Error> {
+        self.headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {token}"))?,
+        );
+
+        Ok(())
+    }
+
+    /// The project ref, i.e. the subdomain of a `https://<project ref>.supabase.co` project url
+    ///
+    /// Returns `None` for self-hosted instances and custom domains.
+    ///
+    /// # Example
+    /// ```
+    /// let client = StorageClient::new("https://abcdefghijklmnop.supabase.co".to_string(), api_key);
+    /// assert_eq!(client.project_ref(), Some("abcdefghijklmnop"));
+    /// ```
+    pub fn project_ref(&self) -> Option<&str> {
+        let (_, rest) = self.project_url.split_once("://")?;
+        let host = rest.split(['/', '?', '#']).next()?.split(':').next()?;
+
+        host.strip_suffix(".supabase.co")
+            .filter(|project_ref| !project_ref.is_empty() && !project_ref.contains('.'))
+    }
+
+    /// Whether the client was created with a non-empty api key
+    ///
+    /// # Example
+    /// ```
+    /// assert!(client.has_api_key());
+    /// ```
+    pub fn has_api_key(&self) -> bool {
+        !self.api_key.trim().is_empty()
+    }
+
+    /// A redacted form of the api key that's safe to log, revealing only its last 4 characters
+    ///
+    /// Keys shorter than 12 characters are fully redacted, as their suffix would give away too
+    /// much of the key. The client's `Debug` output shows the key this way too, so prefer it over
+    /// logging the `api_key` field.
+    ///
+    /// # Example
+    /// ```
+    /// let client = StorageClient::new(project_url, "sb_secret_abcdefgh1234".to_string());
+    /// assert_eq!(client.api_key_fingerprint(), "****1234");
+    /// ```
+    pub fn api_key_fingerprint(&self) -> String {
+        models::api_key_fingerprint(&self.api_key)
+    }
+
     /// Create a new storage bucket, returning the name **_(not the id)_** of the bucket on success.
     ///
     /// Requires your StorageClient to have the following RLS permissions:
@@ -70,11 +390,23 @@ impl StorageClient {
     ///
     /// WARNING: Do not use underscores in bucket names or ids
     ///
+    /// Fails with `Error::AlreadyExists` if a bucket with the same id already exists, or with
+    /// `Error::Validation` listing the invalid fields if the API rejects an option, e.g. a
+    /// size limit above the project's limit.
+    ///
+    /// `allowed_mime_types` accepts `MimeType`s or plain strings, e.g. from config. Pass
+    /// `None::<Vec<MimeType>>` to allow all mime types.
+    ///
     /// # Example
     ///
     ///```rust
     /// let name = client
-    ///     .create_bucket("a-cool-name-for-a-bucket", None, false, None, None)
+    ///     .create_bucket("a-cool-name-for-a-bucket", None, false, None::<Vec<MimeType>>, None)
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// let name = client
+    ///     .create_bucket("images", None, true, Some(["image/png", "image/*"]), None)
     ///     .await
     ///     .unwrap();
     ///```
@@ -83,11 +415,11 @@ impl StorageClient {
         name: &str,
         id: Option<&str>,
         public: bool,
-        allowed_mime_types: Option<Vec<MimeType<'a>>>,
+        allowed_mime_types: Option<impl IntoIterator<Item = impl Into<MimeType<'a>>>>,
         file_size_limit: Option<u64>,
     ) -> Result<String, Error> {
         let mut headers = self.headers.clone();
-        headers.insert(HEADER_API_KEY, HeaderValue::from_str(&self.api_key)?);
+        self.insert_api_key_header(&mut headers)?;
         if !headers.contains_key(AUTHORIZATION) {
             headers.insert(
                 AUTHORIZATION,
@@ -97,11 +429,10 @@ impl StorageClient {
         headers.insert(CONTENT_TYPE, HeaderValue::from_str("application/json")?);
 
         // Convert MimeType enums to their string representations
-        let mime_types: Option<Vec<String>> =
-            allowed_mime_types.map(|types| types.iter().map(|mime| mime.to_string()).collect());
+        let mime_types = mime_type_strings(allowed_mime_types)?;
 
         let payload = CreateBucket {
-            id: Some(id.map(Into::into).unwrap_or_else(|| name)),
+            id: Some(id.unwrap_or(name)),
             name,
             public,
             allowed_mime_types: mime_types,
@@ -115,19 +446,102 @@ impl StorageClient {
             .post(format!("{}{}/bucket", self.project_url, STORAGE_V1))
             .headers(headers)
             .body(request_body)
-            .send()
+            .query(&self.query)
+            .send_with_method(self)
             .await?;
 
-        let res_status = res.status();
-        let res_body = res.text().await?;
+        let parts = read_response(res).await?;
+
+        if !parts.status.is_success() {
+            let duplicate = parts.status == StatusCode::CONFLICT
+                || serde_json::from_str::<StorageErrorBody>(&parts.body)
+                    .is_ok_and(|error_body| error_body.is_duplicate());
+
+            if duplicate {
+                return Err(Error::AlreadyExists {
+                    name: name.to_string(),
+                });
+            }
+
+            return Err(validation_error(response_error(parts)));
+        }
+
+        // The response shape varies between API versions, so any success falls back to the
+        // requested name
+        let bucket: CreateBucketResponse = serde_json::from_str(&parts.body).unwrap_or_default();
+
+        Ok(bucket.name.unwrap_or_else(|| name.to_string()))
+    }
+
+    /// Create a new storage bucket from `BucketOptions`, returning the name **_(not the id)_** of
+    /// the bucket on success
+    ///
+    /// This is the same as `create_bucket`, with the visibility spelled out as a `Visibility`.
+    ///
+    /// # Example
+    /// ```rust
+    /// let options = BucketOptions {
+    ///     visibility: Visibility::Private,
+    ///     file_size_limit: Some(1_000_000),
+    ///     ..Default::default()
+    /// };
+    /// let name = client.create_bucket_with_options("avatars", options).await.unwrap();
+    /// ```
+    pub async fn create_bucket_with_options(
+        &self,
+        name: &str,
+        options: BucketOptions<'_>,
+    ) -> Result<String, Error> {
+        self.create_bucket(
+            name,
+            options.id,
+            options.visibility.is_public(),
+            options.allowed_mime_types,
+            options.file_size_limit,
+        )
+        .await
+    }
+
+    /// Get the bucket with the given name, creating it first if it doesn't exist
+    ///
+    /// The bucket is looked up by `options.id`, falling back to `name`, which makes this safe to
+    /// call repeatedly from setup scripts. Only a missing bucket leads to creating it, any other
+    /// error from the lookup is returned as is.
+    ///
+    /// # Example
+    /// ```rust
+    /// let bucket = client.ensure_bucket("avatars", None).await.unwrap();
+    /// ```
+    pub async fn ensure_bucket(
+        &self,
+        name: &str,
+        options: Option<BucketOptions<'_>>,
+    ) -> Result<Bucket, Error> {
+        let options = options.unwrap_or_default();
+        let id = options.id.unwrap_or(name);
+
+        match self.get_bucket(id).await {
+            Ok(bucket) => return Ok(bucket),
+            Err(err) if is_bucket_not_found(&err) => {}
+            Err(err) => return Err(err),
+        }
 
-        let bucket: CreateBucketResponse =
-            serde_json::from_str(&res_body).map_err(|_| Error::StorageError {
-                status: res_status,
-                message: res_body,
-            })?;
+        // Another caller may have created the bucket since it was looked up
+        match self
+            .create_bucket_with_options(
+                name,
+                BucketOptions {
+                    id: Some(id),
+                    ..options
+                },
+            )
+            .await
+        {
+            Ok(_) | Err(Error::AlreadyExists { .. }) => {}
+            Err(err) => return Err(err),
+        }
 
-        Ok(bucket.name)
+        self.get_bucket(id).await
     }
 
     /// Delete the bucket with the given id
@@ -149,19 +563,18 @@ impl StorageClient {
             .client
             .delete(format!("{}{}/bucket/{}", self.project_url, STORAGE_V1, id))
             .headers(headers)
-            .send()
+            .query(&self.query)
+            .send_with_method(self)
             .await?;
 
-        let res_status = res.status();
-        let res_body = res.text().await?;
+        self.invalidate_allowed_mime_types(Some(id));
 
-        if res_status.is_success() {
-            return Ok(());
+        let parts = read_response(res).await?;
+
+        if parts.status.is_success() {
+            Ok(())
         } else {
-            return Err(Error::StorageError {
-                status: res_status,
-                message: res_body,
-            });
+            Err(response_error(parts))
         }
     }
 
@@ -190,16 +603,11 @@ impl StorageClient {
                 self.project_url, STORAGE_V1, bucket_id
             ))
             .headers(headers)
-            .send()
+            .query(&self.query)
+            .send_with_method(self)
             .await?;
 
-        let res_status = res.status();
-        let res_body = res.text().await?;
-
-        let bucket: Bucket = serde_json::from_str(&res_body).map_err(|_| Error::StorageError {
-            status: res_status,
-            message: res_body,
-        })?;
+        let bucket: Bucket = parse_response(res).await?;
 
         Ok(bucket)
     }
@@ -223,38 +631,53 @@ impl StorageClient {
             .client
             .get(format!("{}{}/bucket", self.project_url, STORAGE_V1))
             .headers(headers)
-            .send()
+            .query(&self.query)
+            .send_with_method(self)
             .await?;
 
-        let res_status = res.status();
-        let res_body = res.text().await?;
-
-        let buckets = serde_json::from_str(&res_body).map_err(|_| Error::StorageError {
-            status: res_status,
-            message: res_body,
-        })?;
+        let buckets = parse_response(res).await?;
 
         Ok(buckets)
     }
 
+    /// Retrieves all Storage buckets within an existing project, keyed by bucket id
+    /// # Example
+    /// ```
+    /// let buckets = client.buckets_by_id().await.unwrap();
+    /// let avatars = buckets.get("avatars");
+    /// ```
+    pub async fn buckets_by_id(&self) -> Result<HashMap<String, Bucket>, Error> {
+        let buckets = self.list_buckets().await?;
+
+        Ok(buckets
+            .into_iter()
+            .map(|bucket| (bucket.id.clone(), bucket))
+            .collect())
+    }
+
     /// Updates a Storage bucket
     ///
     /// Requires the following RLS permissions:
     /// `buckets` table: `select` and `update`
     ///
+    /// Fails with `Error::Validation` listing the invalid fields if the API rejects an option.
+    ///
     /// # Example
     /// ```rust
-    /// client.update_bucket("bucket_id", true, None, Some(100_000_000)).await.unwrap();
+    /// client
+    ///     .update_bucket("bucket_id", true, None::<Vec<MimeType>>, Some(100_000_000))
+    ///     .await
+    ///     .unwrap();
     /// ```
     pub async fn update_bucket<'a>(
         &self,
         id: &str,
         public: bool,
-        allowed_mime_types: Option<Vec<MimeType<'a>>>,
+        allowed_mime_types: Option<impl IntoIterator<Item = impl Into<MimeType<'a>>>>,
         file_size_limit: Option<u64>,
     ) -> Result<String, Error> {
         let mut headers = self.headers.clone();
-        headers.insert(HEADER_API_KEY, HeaderValue::from_str(&self.api_key)?);
+        self.insert_api_key_header(&mut headers)?;
         if !headers.contains_key(AUTHORIZATION) {
             headers.insert(
                 AUTHORIZATION,
@@ -264,8 +687,7 @@ impl StorageClient {
         headers.insert(CONTENT_TYPE, HeaderValue::from_str("application/json")?);
 
         // Convert MimeType enums to their string representations
-        let mime_types: Option<Vec<String>> =
-            allowed_mime_types.map(|types| types.iter().map(|mime| mime.to_string()).collect());
+        let mime_types = mime_type_strings(allowed_mime_types)?;
 
         let payload = UpdateBucket {
             id,
@@ -281,29 +703,55 @@ impl StorageClient {
             .put(format!("{}{}/bucket/{}", self.project_url, STORAGE_V1, id))
             .headers(headers)
             .body(request_body)
-            .send()
+            .query(&self.query)
+            .send_with_method(self)
             .await?;
 
-        let res_status = res.status();
-        let res_body = res.text().await?;
+        self.invalidate_allowed_mime_types(Some(id));
 
-        let bucket: BucketResponse =
-            serde_json::from_str(&res_body).map_err(|_| Error::StorageError {
-                status: res_status,
-                message: res_body,
-            })?;
+        let bucket = parse_bucket_response(res).await.map_err(validation_error)?;
 
         Ok(bucket.message)
     }
 
+    /// Updates a Storage bucket from `UpdateBucketOptions`
+    ///
+    /// This is the same as `update_bucket`, with the visibility spelled out as a `Visibility`.
+    ///
+    /// # Example
+    /// ```rust
+    /// let options = UpdateBucketOptions {
+    ///     visibility: Visibility::Public,
+    ///     ..Default::default()
+    /// };
+    /// client.update_bucket_with_options("bucket_id", options).await.unwrap();
+    /// ```
+    pub async fn update_bucket_with_options(
+        &self,
+        id: &str,
+        options: UpdateBucketOptions<'_>,
+    ) -> Result<String, Error> {
+        self.update_bucket(
+            id,
+            options.visibility.is_public(),
+            options.allowed_mime_types,
+            options.file_size_limit,
+        )
+        .await
+    }
+
     /// Empty a bucket with a given id
+    ///
+    /// Returns `Outcome::Success` for any 2xx response, whatever the server's message.
+    ///
     /// # Example
     /// ```rust
-    /// let empty = client.empty_bucket("empty_bucket_test").await.unwrap();
+    /// let outcome = client.empty_bucket("empty_bucket_test").await.unwrap();
+    /// assert!(outcome.is_success());
     /// ```
-    pub async fn empty_bucket(&self, id: &str) -> Result<String, Error> {
+    pub async fn empty_bucket(&self, id: &str) -> Result<Outcome, Error> {
         let mut headers = self.headers.clone();
-        headers.insert(HEADER_API_KEY, HeaderValue::from_str(&self.api_key)?); // maybe delete
+        self.insert_api_key_header(&mut headers)?;
         if !headers.contains_key(AUTHORIZATION) {
             headers.insert(
                 AUTHORIZATION,
@@ -318,19 +766,33 @@ impl StorageClient {
                 self.project_url, STORAGE_V1, id
             ))
             .headers(headers)
-            .send()
+            .query(&self.query)
+            .send_with_method(self)
             .await?;
 
-        let res_status = res.status();
-        let res_body = res.text().await?;
-
-        let bucket: BucketResponse =
-            serde_json::from_str(&res_body).map_err(|_| Error::StorageError {
-                status: res_status,
-                message: res_body,
-            })?;
+        parse_outcome(res).await
+    }
 
-        Ok(bucket.message)
+    /// Empty a bucket client-side by listing every object and deleting them in batches,
+    /// returning the number of objects deleted
+    ///
+    /// This is a fallback for instances where `empty_bucket` isn't available, and descends into
+    /// nested folders. Like `delete_folder`, it fails with `Error::InvalidArgument` without
+    /// deleting anything if folders are nested deeper than `MAX_FOLDER_DEPTH`.
+    ///
+    /// At most `max_concurrency` delete requests run at once, defaulting to the client's
+    /// `with_max_concurrency` setting.
+    ///
+    /// # Example
+    /// ```rust
+    /// let deleted = client.empty_bucket_manual("empty_bucket_test", None).await.unwrap();
+    /// ```
+    pub async fn empty_bucket_manual(
+        &self,
+        id: &str,
+        max_concurrency: Option<usize>,
+    ) -> Result<usize, Error> {
+        self.delete_folder(id, "", max_concurrency).await
     }
 
     async fn upload_or_update_file(
@@ -340,6 +802,30 @@ impl StorageClient {
         path: &str,
         update: bool,
         options: Option<FileOptions<'_>>,
+    ) -> Result<ObjectResponse, Error> {
+        #[cfg(feature = "infer")]
+        let options = options.map(|mut opts| {
+            if opts.detect_content_type && opts.content_type.is_none() {
+                opts.content_type = infer::get(&data).map(|kind| kind.mime_type());
+            }
+            opts
+        });
+
+        let streaming = options.as_ref().is_some_and(|opts| opts.duplex.is_some());
+        let body = upload_body(data, streaming);
+
+        self.upload_or_update_body(bucket_id, body, path, update, None, options)
+            .await
+    }
+
+    async fn upload_or_update_body(
+        &self,
+        bucket_id: &str,
+        body: Body,
+        path: &str,
+        update: bool,
+        content_length: Option<u64>,
+        options: Option<FileOptions<'_>>,
     ) -> Result<ObjectResponse, Error> {
         let mut headers = self.headers.clone();
         if !headers.contains_key(AUTHORIZATION) {
@@ -349,73 +835,93 @@ impl StorageClient {
             );
         }
 
+        // Streamed bodies are sent chunked unless the caller knows their length
+        if let Some(content_length) = content_length {
+            headers.insert(CONTENT_LENGTH, HeaderValue::from(content_length));
+        }
+
         // Set optional headers
+        let mut upsert = false;
         if let Some(opts) = options {
-            if let Some(cache_control) = opts.cache_control {
-                headers.insert(
-                    CACHE_CONTROL,
-                    HeaderValue::from_str(&format!("{}", cache_control.as_secs()))?,
-                );
-            }
+            insert_file_option_headers(&mut headers, &opts)?;
+            self.validate_mime_type(bucket_id, &opts).await?;
+            upsert = opts.upsert;
+        }
+        self.insert_default_cache_control(&mut headers);
 
-            if let Some(content_type) = opts.content_type {
-                headers.insert(
-                    CONTENT_TYPE,
-                    HeaderValue::from_str(&format!("{}", content_type))?,
-                );
-            }
+        let method = match update {
+            true => Method::PUT,
+            false => Method::POST,
+        };
 
-            if opts.upsert {
-                headers.insert(
-                    "x-upsert",
-                    HeaderValue::from_str(&format!("{}", opts.upsert))?,
-                );
-            }
-        }
+        let request = self
+            .client
+            .request(
+                method,
+                format!(
+                    "{}{}/object/{}/{}",
+                    self.project_url, STORAGE_V1, bucket_id, path
+                ),
+            )
+            .headers(headers)
+            .body(body)
+            .query(&self.query);
 
-        let res = match update {
-            true => {
-                self.client
-                    .put(format!(
-                        "{}{}/object/{}/{}",
-                        self.project_url, STORAGE_V1, bucket_id, path
-                    ))
-                    .headers(headers)
-                    .body(data)
-                    .send()
-                    .await?
-            }
-            false => {
-                self.client
-                    .post(format!(
-                        "{}{}/object/{}/{}",
-                        self.project_url, STORAGE_V1, bucket_id, path
-                    ))
-                    .headers(headers)
-                    .body(data)
-                    .send()
-                    .await?
-            }
+        // A create may have succeeded before an ambiguous failure, so only replacing uploads are
+        // safe to send again
+        let retries = match update || upsert {
+            true => self.max_retries,
+            false => 0,
         };
 
-        let res_status = res.status();
-        let res_body = res.text().await?;
+        let res = send_with_retries(self, request, retries).await?;
 
-        let object: ObjectResponse =
-            serde_json::from_str(&res_body).map_err(|_| Error::StorageError {
-                status: res_status,
-                message: res_body,
-            })?;
+        let object: ObjectResponse = parse_response(res)
+            .await
+            .map_err(|err| object_already_exists(err, bucket_id, path))?;
 
         Ok(object)
     }
 
-    /// Replaces the file at the designated bucket and path with the given `Vec<u8>`
+    /// Inserts the client's default Cache-Control header, unless the upload set its own
+    fn insert_default_cache_control(&self, headers: &mut HeaderMap) {
+        if let Some(cache_control) = &self.default_cache_control {
+            if !headers.contains_key(CACHE_CONTROL) {
+                headers.insert(CACHE_CONTROL, cache_control.clone());
+            }
+        }
+    }
+
+    /// Rejects a signed URL expiry of zero, or longer than `with_max_signed_url_expiry` allows
+    fn check_signed_url_expiry(&self, expires_in: u64) -> Result<(), Error> {
+        if expires_in == 0 || expires_in > self.max_signed_url_expiry {
+            return Err(Error::InvalidArgument {
+                message: format!(
+                    "expires_in must be between 1 and {} seconds, got {expires_in}",
+                    self.max_signed_url_expiry
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Inserts the `apikey` header, unless disabled with `with_send_api_key_header`
+    fn insert_api_key_header(&self, headers: &mut HeaderMap) -> Result<(), Error> {
+        if self.send_api_key_header {
+            headers.insert(HEADER_API_KEY, HeaderValue::from_str(&self.api_key)?);
+        }
+        Ok(())
+    }
+
+    /// Replaces the file at the designated bucket and path with the given `Vec<u8>`
+    ///
+    /// Deprecated alias of `update_file`, which matches the name used by the official clients.
     ///
     /// # Example
     /// ```rust
     /// let object = client.replace_file("bucket_id", file, "path/to/file.txt", Some(options)).await.unwrap();
     /// ```
+    #[deprecated(note = "use `update_file` instead, `replace_file` is an alias of it")]
     pub async fn replace_file(
         &self,
         bucket_id: &str,
@@ -423,13 +929,10 @@ impl StorageClient {
         path: &str,
         options: Option<FileOptions<'_>>,
     ) -> Result<ObjectResponse, Error> {
-        self.upload_or_update_file(bucket_id, data, path, true, options)
-            .await
+        self.update_file(bucket_id, data, path, options).await
     }
 
-    /// Updates the file at the designated bucket and path with the given `Vec<u8>`
-    ///
-    /// This is identical to `replace_file`
+    /// Replaces the file at the designated bucket and path with the given `Vec<u8>`
     ///
     /// # Example
     /// ```rust
@@ -463,6 +966,49 @@ impl StorageClient {
             .await
     }
 
+    /// Uploads a file at the designated bucket and path from a stream of chunks
+    ///
+    /// When `content_length` is known it is sent as the `Content-Length` header, which some
+    /// server configurations require. Otherwise the body is sent with chunked transfer encoding.
+    /// If the stream doesn't yield exactly `content_length` bytes the upload is aborted and fails
+    /// with `Error::ContentLengthMismatch`.
+    ///
+    /// # Example
+    /// ```rust
+    /// let chunks = futures_util::stream::iter(vec![Ok::<_, std::io::Error>(bytes)]);
+    /// let object = client
+    ///     .upload_file_stream("bucket_id", chunks, "path/to/file.txt", Some(len), None)
+    ///     .await
+    ///     .unwrap();
+    /// ```
+    pub async fn upload_file_stream<S>(
+        &self,
+        bucket_id: &str,
+        stream: S,
+        path: &str,
+        content_length: Option<u64>,
+        options: Option<FileOptions<'_>>,
+    ) -> Result<ObjectResponse, Error>
+    where
+        S: TryStream + Send + 'static,
+        S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+        Bytes: From<S::Ok>,
+    {
+        let progress = Arc::new(StreamProgress::default());
+        let result = self
+            .upload_or_update_body(
+                bucket_id,
+                length_checked_body(stream, content_length, progress.clone()),
+                path,
+                false,
+                content_length,
+                options,
+            )
+            .await;
+
+        check_content_length(result, content_length, &progress)
+    }
+
     // TODO: Incorporate download options
     /// Download the designated file
     /// # Example
@@ -475,7 +1021,239 @@ impl StorageClient {
         path: &str,
         options: Option<DownloadOptions<'_>>,
     ) -> Result<Vec<u8>, Error> {
+        let bytes = self.download_file_bytes(bucket_id, path, options).await?;
+
+        Ok(bytes.into())
+    }
+
+    /// Download several files from the same bucket, returning their contents in the order of
+    /// `paths`
+    ///
+    /// At most `max_concurrency` downloads run at once, defaulting to the client's
+    /// `with_max_concurrency` setting. The first failed download is returned as the error.
+    ///
+    /// # Example
+    /// ```rust
+    /// let files = client
+    ///     .download_files("bucket_id", vec!["1.txt", "2.txt"], None)
+    ///     .await
+    ///     .unwrap();
+    /// ```
+    pub async fn download_files(
+        &self,
+        bucket_id: &str,
+        paths: Vec<&str>,
+        max_concurrency: Option<usize>,
+    ) -> Result<Vec<Vec<u8>>, Error> {
+        let max_concurrency = max_concurrency.unwrap_or(self.max_concurrency).max(1);
+        let downloads = paths
+            .into_iter()
+            .map(|path| self.download_file(bucket_id, path, None));
+
+        stream::iter(downloads)
+            .buffered(max_concurrency)
+            .try_collect()
+            .await
+    }
+
+    /// Download the designated file as `Bytes`, without copying it into a `Vec<u8>`
+    ///
+    /// # Example
+    /// ```rust
+    /// let bytes = client.download_file_bytes("bucket_id", "path/to/file.txt", None).await.unwrap();
+    /// ```
+    pub async fn download_file_bytes(
+        &self,
+        bucket_id: &str,
+        path: &str,
+        options: Option<DownloadOptions<'_>>,
+    ) -> Result<Bytes, Error> {
+        let res = self.download(bucket_id, path, options).await?;
+
+        Ok(res.bytes().await?)
+    }
+
+    /// Download the designated file as a stream of chunks, calling `on_progress` after each one
+    ///
+    /// `on_progress` receives the number of bytes received so far and the total size from the
+    /// `Content-Length` header, or `None` when the server doesn't send one. The file is never
+    /// buffered in full, which makes this suitable for progress bars on large downloads.
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut stream = client
+    ///     .download_file_stream_with_progress("bucket_id", "path/to/file.mp4", None, |received, total| {
+    ///         println!("{received} of {total:?} bytes");
+    ///     })
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// while let Some(chunk) = stream.next().await {
+    ///     file.write_all(&chunk.unwrap()).await.unwrap();
+    /// }
+    /// ```
+    pub async fn download_file_stream_with_progress<F>(
+        &self,
+        bucket_id: &str,
+        path: &str,
+        options: Option<DownloadOptions<'_>>,
+        mut on_progress: F,
+    ) -> Result<impl Stream<Item = Result<Bytes, Error>>, Error>
+    where
+        F: FnMut(u64, Option<u64>) + Send + 'static,
+    {
+        let res = self.download(bucket_id, path, options).await?;
+        let total = res.content_length();
+        let mut received = 0;
+
+        Ok(res.bytes_stream().map(move |chunk| {
+            let chunk = chunk?;
+            received += chunk.len() as u64;
+            on_progress(received, total);
+
+            Ok(chunk)
+        }))
+    }
+
+    /// Download the designated file, along with the `Content-Type` and `Content-Encoding` it was
+    /// served with
+    ///
+    /// Objects uploaded with a `Content-Encoding` (e.g. `gzip`) are returned as stored. Enable the
+    /// `decompress` feature to have gzip and brotli bodies decoded transparently.
+    ///
+    /// # Example
+    /// ```rust
+    /// let response = client.download_file_response("bucket_id", "path/to/file.txt", None).await.unwrap();
+    /// if let Some(encoding) = response.content_encoding {
+    ///     println!("still encoded with {encoding}");
+    /// }
+    /// ```
+    pub async fn download_file_response(
+        &self,
+        bucket_id: &str,
+        path: &str,
+        options: Option<DownloadOptions<'_>>,
+    ) -> Result<DownloadResponse, Error> {
+        let res = self.download(bucket_id, path, options).await?;
+
+        download_response(res).await
+    }
+
+    /// Download the designated file only if it no longer matches `etag`
+    ///
+    /// `etag` is sent as `If-None-Match`. Returns `ConditionalDownload::NotModified` when the server
+    /// answers `304 Not Modified`, so a cached copy can be reused without downloading it again.
+    /// The `ETag` to store for next time is `DownloadResponse::etag`.
+    ///
+    /// # Example
+    /// ```rust
+    /// match client
+    ///     .download_file_if_modified("bucket_id", "path/to/file.txt", &cached.etag, None)
+    ///     .await
+    ///     .unwrap()
+    /// {
+    ///     ConditionalDownload::Modified(response) => cache.store(response),
+    ///     ConditionalDownload::NotModified => {}
+    /// }
+    /// ```
+    pub async fn download_file_if_modified(
+        &self,
+        bucket_id: &str,
+        path: &str,
+        etag: &str,
+        options: Option<DownloadOptions<'_>>,
+    ) -> Result<ConditionalDownload, Error> {
+        let mut headers = HeaderMap::new();
+        headers.insert(IF_NONE_MATCH, HeaderValue::from_str(etag)?);
+
+        let res = self
+            .send_download(bucket_id, path, options, headers)
+            .await?;
+
+        if res.status() == StatusCode::NOT_MODIFIED {
+            return Ok(ConditionalDownload::NotModified);
+        }
+
+        if !res.status().is_success() {
+            return Err(response_error(read_response(res).await?));
+        }
+
+        Ok(ConditionalDownload::Modified(download_response(res).await?))
+    }
+
+    /// Upload a file, aborting the request if `token` is cancelled first
+    ///
+    /// Returns `Error::Cancelled` when the upload was aborted.
+    ///
+    /// # Example
+    /// ```rust
+    /// let token = CancellationToken::new();
+    /// let object = client
+    ///     .upload_file_with_cancellation("bucket_id", file, "path/to/file.txt", None, token.clone())
+    ///     .await;
+    /// ```
+    #[cfg(feature = "cancellation")]
+    pub async fn upload_file_with_cancellation(
+        &self,
+        bucket_id: &str,
+        data: Vec<u8>,
+        path: &str,
+        options: Option<FileOptions<'_>>,
+        token: CancellationToken,
+    ) -> Result<ObjectResponse, Error> {
+        run_cancellable(token, self.upload_file(bucket_id, data, path, options)).await
+    }
+
+    /// Download a file, aborting the request if `token` is cancelled first
+    ///
+    /// Returns `Error::Cancelled` when the download was aborted.
+    ///
+    /// # Example
+    /// ```rust
+    /// let token = CancellationToken::new();
+    /// let file = client
+    ///     .download_file_with_cancellation("bucket_id", "path/to/file.txt", None, token.clone())
+    ///     .await;
+    /// ```
+    #[cfg(feature = "cancellation")]
+    pub async fn download_file_with_cancellation(
+        &self,
+        bucket_id: &str,
+        path: &str,
+        options: Option<DownloadOptions<'_>>,
+        token: CancellationToken,
+    ) -> Result<Vec<u8>, Error> {
+        run_cancellable(token, self.download_file(bucket_id, path, options)).await
+    }
+
+    /// Request the designated file, returning the response once it has succeeded
+    async fn download(
+        &self,
+        bucket_id: &str,
+        path: &str,
+        options: Option<DownloadOptions<'_>>,
+    ) -> Result<Response, Error> {
+        let res = self
+            .send_download(bucket_id, path, options, HeaderMap::new())
+            .await?;
+
+        if !res.status().is_success() {
+            return Err(response_error(read_response(res).await?));
+        }
+
+        Ok(res)
+    }
+
+    /// Send a download request with `extra_headers`, leaving the response status to the caller
+    async fn send_download(
+        &self,
+        bucket_id: &str,
+        path: &str,
+        options: Option<DownloadOptions<'_>>,
+        extra_headers: HeaderMap,
+    ) -> Result<Response, Error> {
         let mut headers = self.headers.clone();
+        headers.extend(extra_headers);
         if !headers.contains_key(AUTHORIZATION) {
             headers.insert(
                 AUTHORIZATION,
@@ -484,33 +1262,35 @@ impl StorageClient {
         }
 
         let mut renderpath = "object";
-        if let Some(opts) = options {
-            if opts.transform.is_some() {
-                renderpath = "render/image/authenticated"
+        if let Some(transform) = options.as_ref().and_then(|opts| opts.transform.as_ref()) {
+            renderpath = "render/image/authenticated";
+
+            // Let the server pick the output format from `Accept`, unless one was set explicitly
+            if let Some(accept) = transform.accept_header() {
+                if !headers.contains_key(ACCEPT) {
+                    headers.insert(ACCEPT, HeaderValue::from_str(&accept)?);
+                }
             }
         }
 
+        let url_str = format!(
+            "{}{}/{}/{}/{}",
+            self.project_url, STORAGE_V1, renderpath, bucket_id, path
+        );
+        let url = match options {
+            Some(opts) => build_url_with_options(&url_str, &opts)?,
+            None => url_str,
+        };
+
         let res = self
             .client
-            .get(format!(
-                "{}{}/{}/{}/{}",
-                self.project_url, STORAGE_V1, renderpath, bucket_id, path
-            ))
+            .get(url)
             .headers(headers)
-            .send()
+            .query(&self.query)
+            .send_with_method(self)
             .await?;
 
-        let res_status = res.status();
-        let res_body = res.bytes().await?.to_vec();
-
-        if !res_status.is_success() {
-            return Err(Error::StorageError {
-                status: res_status,
-                message: String::from_utf8_lossy(&res_body).to_string(),
-            });
-        }
-
-        Ok(res_body)
+        Ok(res)
     }
 
     /// Delete the designated file, returning a confirmation message on success
@@ -537,21 +1317,121 @@ impl StorageClient {
                 self.project_url, STORAGE_V1, bucket_id, path
             ))
             .headers(headers)
-            .send()
+            .query(&self.query)
+            .send_with_method(self)
             .await?;
 
-        let res_status = res.status();
-        let res_body = res.text().await?;
-
-        let message: BucketResponse =
-            serde_json::from_str(&res_body).map_err(|_| Error::StorageError {
-                status: res_status,
-                message: res_body,
-            })?;
+        let message = parse_bucket_response(res).await?;
 
         Ok(message)
     }
 
+    /// Delete the designated file if it exists, returning whether it was deleted
+    ///
+    /// Unlike `delete_file`, a missing object isn't an error, which makes the delete idempotent.
+    ///
+    /// # Example
+    /// ```rust
+    /// let deleted = client
+    ///     .delete_file_if_exists("upload_tests", "tests/signed_upload")
+    ///     .await
+    ///     .unwrap();
+    /// ```
+    pub async fn delete_file_if_exists(&self, bucket_id: &str, path: &str) -> Result<bool, Error> {
+        match self
+            .delete_file(bucket_id, path)
+            .await
+            .map_err(|err| source_not_found(err, bucket_id, path))
+        {
+            Ok(_) => Ok(true),
+            Err(Error::NotFound { .. }) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Delete multiple files at once, returning the deleted objects on success
+    ///
+    /// The API accepts at most `DELETE_BATCH_SIZE` paths per call.
+    ///
+    /// # Example
+    /// ```rust
+    /// let deleted = client
+    ///     .delete_files("bucket_id", vec!["1.txt", "folder/2.txt"])
+    ///     .await
+    ///     .unwrap();
+    /// ```
+    pub async fn delete_files(
+        &self,
+        bucket_id: &str,
+        paths: Vec<&str>,
+    ) -> Result<Vec<FileObject>, Error> {
+        let mut headers = self.headers.clone();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_str("application/json")?);
+        if !headers.contains_key(AUTHORIZATION) {
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {}", &self.api_key))?,
+            );
+        }
+
+        let payload = DeleteFilesPayload { prefixes: paths };
+
+        let body = serde_json::to_string(&payload)?;
+
+        let res = self
+            .client
+            .delete(format!(
+                "{}{}/object/{}",
+                self.project_url, STORAGE_V1, bucket_id
+            ))
+            .headers(headers)
+            .body(body)
+            .query(&self.query)
+            .send_with_method(self)
+            .await?;
+
+        let files: Vec<FileObject> = parse_response(res).await?;
+
+        Ok(files)
+    }
+
+    /// Delete every object under `prefix`, including nested folders and their placeholders,
+    /// returning the number of objects deleted
+    ///
+    /// Objects are deleted in batches of `DELETE_BATCH_SIZE`. Every object is listed before any is
+    /// deleted, so folders nested deeper than `MAX_FOLDER_DEPTH` fail with
+    /// `Error::InvalidArgument` without deleting anything.
+    ///
+    /// At most `max_concurrency` delete requests run at once, defaulting to the client's
+    /// `with_max_concurrency` setting.
+    ///
+    /// # Example
+    /// ```rust
+    /// let deleted = client
+    ///     .delete_folder("bucket_id", "folder/subfolder", None)
+    ///     .await
+    ///     .unwrap();
+    /// ```
+    pub async fn delete_folder(
+        &self,
+        bucket_id: &str,
+        prefix: &str,
+        max_concurrency: Option<usize>,
+    ) -> Result<usize, Error> {
+        let max_concurrency = max_concurrency.unwrap_or(self.max_concurrency).max(1);
+        let paths = self.list_object_paths(bucket_id, prefix).await?;
+
+        let batches = paths
+            .chunks(DELETE_BATCH_SIZE)
+            .map(|batch| self.delete_files(bucket_id, batch.iter().map(String::as_str).collect()));
+        stream::iter(batches)
+            .buffer_unordered(max_concurrency)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        Ok(paths.len())
+    }
+
     /// List all files that match your search criteria
     ///
     /// The returned `Vec<FileObject>` will contain both files and folders. Folders can be
@@ -568,6 +1448,7 @@ impl StorageClient {
     ///         order: Order::Asc, // In Ascending order
     ///     }),
     ///     search: None, // With no specific search string
+    ///     include_metadata: None, // Keep each object's metadata
     /// };
     ///
     /// client
@@ -591,6 +1472,9 @@ impl StorageClient {
         }
 
         let options = options.unwrap_or_default();
+        check_list_sort(options.sort_by.as_ref())?;
+
+        let include_metadata = options.include_metadata.unwrap_or(true);
         let payload = ListFilesPayload {
             limit: options.limit,
             offset: options.offset,
@@ -609,429 +1493,1634 @@ impl StorageClient {
             ))
             .headers(headers)
             .body(body)
-            .send()
+            .query(&self.query)
+            .send_with_method(self)
             .await?;
 
-        let res_status = res.status();
-        let res_body = res.text().await?;
+        let mut files: Vec<FileObject> = parse_response(res).await?;
 
-        let files: Vec<FileObject> =
-            serde_json::from_str(&res_body).map_err(|_| Error::StorageError {
-                status: res_status,
-                message: res_body,
-            })?;
+        if !include_metadata {
+            files.iter_mut().for_each(|file| file.metadata = None);
+        }
 
         Ok(files)
     }
 
-    /// Copy a file from one path to another
-    /// # Example
+    /// List one page of files, along with whether more pages probably follow, e.g. to render a
+    /// "load more" control
     ///
+    /// The page size is `options.limit`, defaulting to `LIST_PAGE_SIZE`. The API doesn't report
+    /// a total, so `has_more` is inferred from the page being full. When the remaining entries
+    /// exactly fill the last page, `has_more` is `true` and the next page comes back empty.
+    ///
+    /// # Example
     /// ```rust
-    /// // Copies `3.txt` into `folder/4.txt` within the same bucket, including metadata
-    /// let key = client
-    ///     .copy_file("from_bucket", None, "3.txt", Some("folder/4.txt"), true)
-    ///     .await
-    ///     .unwrap();
+    /// let options = FileSearchOptions {
+    ///     limit: Some(20),
+    ///     offset: Some(40),
+    ///     ..Default::default()
+    /// };
     ///
-    /// // Copies `a.txt` into `folder/b.txt` in a different bucket, including metadata
-    /// let key = client
-    ///     .copy_file("from_bucket", "to_bucket", "a.txt", Some("folder/b.txt"), true)
-    ///     .await
-    ///     .unwrap();
+    /// let page = client.list_files_page("bucket_id", Some("folder"), Some(options)).await.unwrap();
+    /// if page.has_more {
+    ///     // Show a "load more" button
+    /// }
     /// ```
-    pub async fn copy_file(
+    pub async fn list_files_page(
         &self,
-        from_bucket: &str,
-        to_bucket: Option<&str>,
-        from_path: &str,
-        to_path: Option<&str>,
-        copy_metadata: bool,
-    ) -> Result<String, Error> {
-        let mut headers = self.headers.clone();
-        headers.insert(CONTENT_TYPE, HeaderValue::from_str("application/json")?);
-        if !headers.contains_key(AUTHORIZATION) {
-            headers.insert(
-                AUTHORIZATION,
-                HeaderValue::from_str(&format!("Bearer {}", &self.api_key))?,
-            );
-        }
-
-        let payload = CopyFilePayload {
-            bucket_id: from_bucket,
-            source_key: from_path,
-            destination_bucket: to_bucket.unwrap_or(from_bucket),
-            destination_key: to_path.unwrap_or(from_path),
-            copy_metadata,
-        };
-
-        let body = serde_json::to_string(&payload)?;
-
-        let res = self
-            .client
-            .post(format!("{}{}/object/copy", self.project_url, STORAGE_V1,))
-            .headers(headers)
-            .body(body)
-            .send()
-            .await?;
-
-        let res_status = res.status();
-        let res_body = res.text().await?;
+        bucket_id: &str,
+        path: Option<&str>,
+        options: Option<FileSearchOptions<'_>>,
+    ) -> Result<FilePage, Error> {
+        let mut options = options.unwrap_or_default();
+        let limit = *options.limit.get_or_insert(LIST_PAGE_SIZE);
 
-        let value: CopyFileResponse =
-            serde_json::from_str(&res_body).map_err(|_| Error::StorageError {
-                status: res_status,
-                message: res_body,
-            })?;
+        let items = self.list_files(bucket_id, path, Some(options)).await?;
+        let has_more = items.len() >= limit as usize;
 
-        Ok(value.key)
+        Ok(FilePage { items, has_more })
     }
 
-    /// Create a signed download url, returns a signed_url on success
+    /// Lazily list the files inside `path`, fetching pages of `page_size` objects as the stream
+    /// is polled
+    ///
+    /// Unlike `list_files`, this doesn't hold every object in memory at once, which suits large
+    /// buckets. The stream ends after the first short page or the first error. A `page_size` of
+    /// `0` is treated as `1`.
     ///
     /// # Example
     /// ```rust
+    /// let mut files = client.list_files_stream("bucket_id", Some("folder"), 100);
+    /// while let Some(file) = files.next().await {
+    ///     println!("{}", file.unwrap().name);
+    /// }
+    /// ```
+    pub fn list_files_stream<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: Option<&'a str>,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<FileObject, Error>> + 'a {
+        let page_size = page_size.max(1);
+
+        stream::unfold(Some(0), move |offset| async move {
+            let offset = offset?;
+            let options = FileSearchOptions {
+                limit: Some(page_size),
+                offset: Some(offset),
+                ..Default::default()
+            };
+
+            match self.list_files(bucket_id, path, Some(options)).await {
+                Ok(page) => {
+                    let next = (page.len() >= page_size as usize).then(|| offset + page_size);
+                    Some((Ok(page), next))
+                }
+                Err(err) => Some((Err(err), None)),
+            }
+        })
+        .map_ok(|page| stream::iter(page.into_iter().map(Ok)))
+        .try_flatten()
+    }
+
+    /// List the folders directly inside `path`
     ///
-    /// client
-    ///    .create_signed_url("list_files", "3.txt", 12431234)
-    ///    .await
-    ///    .unwrap();
+    /// # Example
+    /// ```rust
+    /// let folders = client.list_folders("bucket_id", Some("folder")).await.unwrap();
     /// ```
-    pub async fn create_signed_url(
+    pub async fn list_folders(
         &self,
         bucket_id: &str,
-        path: &str,
-        expires_in: u64,
-        options: Option<DownloadOptions<'_>>,
-    ) -> Result<String, Error> {
-        let mut headers = self.headers.clone();
-        headers.insert(CONTENT_TYPE, HeaderValue::from_str("application/json")?);
-        if !headers.contains_key(AUTHORIZATION) {
-            headers.insert(
-                AUTHORIZATION,
-                HeaderValue::from_str(&format!("Bearer {}", &self.api_key))?,
-            );
-        }
-
-        let payload = CreateSignedUrlPayload {
-            expires_in,
-            transform: options.and_then(|opts| opts.transform),
-        };
-
-        let body = serde_json::to_string(&payload)?;
-
-        let res = self
-            .client
-            .post(format!(
-                "{}{}/object/sign/{}/{}",
-                self.project_url, STORAGE_V1, bucket_id, path
-            ))
-            .headers(headers)
-            .body(body)
-            .send()
-            .await?;
-
-        let res_status = res.status();
-        let res_body = res.text().await?;
-
-        let signed_url_response: SignedUrlResponse =
-            serde_json::from_str(&res_body).map_err(|_| Error::StorageError {
-                status: res_status,
-                message: res_body,
-            })?;
+        path: Option<&str>,
+    ) -> Result<Vec<FileObject>, Error> {
+        let files = self.list_files(bucket_id, path, None).await?;
 
-        Ok(format!(
-            "{}{}{}",
-            self.project_url, STORAGE_V1, signed_url_response.signed_url
-        ))
+        Ok(files.into_iter().filter(FileObject::is_folder).collect())
     }
 
-    /// Create multiple signed download urls, returns a `Vec` of signed_urls on success
+    /// List the files inside `path` together with a signed URL for each, valid for `expires_in`
+    /// seconds
+    ///
+    /// Folders are skipped, and the files are signed in batches with
+    /// `create_multiple_signed_urls` rather than one request per file. Files that can't be
+    /// signed, e.g. because they were deleted after being listed, are left out.
     ///
     /// # Example
     /// ```rust
+    /// let files = client
+    ///     .list_files_with_signed_urls("bucket_id", Some("gallery"), 3600, None)
+    ///     .await
+    ///     .unwrap();
     ///
-    /// let urls = client
-    ///    .create_multiple_signed_urls("bucket_id", vec!["1.txt", "2.txt", "3.txt"], 100_000)
-    ///    .await
-    ///    .unwrap();
+    /// for (file, url) in files {
+    ///     println!("{}: {url}", file.name);
+    /// }
     /// ```
-    pub async fn create_multiple_signed_urls(
+    pub async fn list_files_with_signed_urls(
         &self,
         bucket_id: &str,
-        paths: Vec<&str>,
+        path: Option<&str>,
         expires_in: u64,
-    ) -> Result<Vec<String>, Error> {
-        let mut headers = self.headers.clone();
-        headers.insert(CONTENT_TYPE, HeaderValue::from_str("application/json")?);
-        if !headers.contains_key(AUTHORIZATION) {
-            headers.insert(
-                AUTHORIZATION,
-                HeaderValue::from_str(&format!("Bearer {}", &self.api_key))?,
-            );
-        }
-
-        let payload = CreateMultipleSignedUrlsPayload { expires_in, paths };
+        options: Option<FileSearchOptions<'_>>,
+    ) -> Result<Vec<(FileObject, String)>, Error> {
+        self.check_signed_url_expiry(expires_in)?;
 
-        let body = serde_json::to_string(&payload)?;
+        let files: Vec<FileObject> = self
+            .list_files(bucket_id, path, options)
+            .await?
+            .into_iter()
+            .filter(|file| !file.is_folder())
+            .collect();
 
-        let res = self
-            .client
-            .post(format!(
-                "{}{}/object/sign/{}",
-                self.project_url, STORAGE_V1, bucket_id
-            ))
-            .headers(headers)
-            .body(body)
-            .send()
+        let prefix = path.unwrap_or("");
+        let paths: Vec<String> = files.iter().map(|file| file.full_path(prefix)).collect();
+        let entries = self
+            .create_multiple_signed_urls(
+                bucket_id,
+                paths.iter().map(String::as_str).collect(),
+                expires_in,
+                None,
+            )
             .await?;
 
-        let res_status = res.status();
-        let res_body = res.text().await?;
-
-        let signed_url_response: Vec<SignedUrlResponse> =
-            serde_json::from_str(&res_body).map_err(|_| Error::StorageError {
-                status: res_status,
-                message: res_body,
-            })?;
-
-        let signed_urls: Vec<String> = signed_url_response
+        Ok(files
             .into_iter()
-            .map(|r| format!("{}{}{}", self.project_url, STORAGE_V1, r.signed_url))
-            .collect();
-
-        Ok(signed_urls)
+            .zip(entries)
+            .filter_map(|(file, entry)| entry.signed_url.map(|url| (file, url)))
+            .collect())
     }
 
-    /// Create a signed upload url,
+    /// List the folder at `root` as a tree of its files and nested folders, e.g. for a file
+    /// explorer
     ///
-    /// Returns the `url` (without hostname) and authorization `token` on success
+    /// Folders are listed one at a time, descending at most `max_depth` levels below `root`,
+    /// which is capped at `MAX_FOLDER_DEPTH`. Folders below that are included, but marked as
+    /// `truncated` with no contents. Pass `None` as `root` for the whole bucket.
     ///
     /// # Example
     /// ```rust
-    /// let signed = client.create_signed_upload_url("list_files", "42.txt").await.unwrap();
+    /// let tree = client.list_tree("bucket_id", Some("photos"), 3).await.unwrap();
+    /// for folder in &tree.folders {
+    ///     println!("{}: {} files", folder.path, folder.file_count());
+    /// }
     /// ```
-    pub async fn create_signed_upload_url(
+    pub async fn list_tree(
         &self,
         bucket_id: &str,
-        path: &str,
-    ) -> Result<SignedUploadUrlResponse, Error> {
-        let mut headers = self.headers.clone();
-        if !headers.contains_key(AUTHORIZATION) {
-            headers.insert(
-                AUTHORIZATION,
-                HeaderValue::from_str(&format!("Bearer {}", &self.api_key))?,
-            );
-        }
-
-        let res = self
-            .client
-            .post(format!(
-                "{}{}/object/upload/sign/{}/{}",
-                self.project_url, STORAGE_V1, bucket_id, path
-            ))
-            .headers(headers)
-            .send()
-            .await?;
-
-        let res_status = res.status();
-        let res_body = res.text().await?;
+        root: Option<&str>,
+        max_depth: usize,
+    ) -> Result<FileTree, Error> {
+        let path = root.unwrap_or("").trim_matches('/');
+        let name = path.rsplit('/').next().unwrap_or("");
+
+        self.list_tree_folder(
+            bucket_id,
+            name.to_string(),
+            path.to_string(),
+            max_depth.min(MAX_FOLDER_DEPTH),
+        )
+        .await
+    }
 
-        let mut response: SignedUploadUrlResponse =
-            serde_json::from_str(&res_body).map_err(|_| Error::StorageError {
-                status: res_status,
-                message: res_body,
-            })?;
+    /// Lists one folder of `list_tree`, recursing into its subfolders while `depth_left` allows
+    fn list_tree_folder<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        name: String,
+        path: String,
+        depth_left: usize,
+    ) -> BoxFuture<'a, Result<FileTree, Error>> {
+        Box::pin(async move {
+            let entries: Vec<FileObject> = self
+                .list_files_stream(bucket_id, Some(&path), LIST_PAGE_SIZE)
+                .try_collect()
+                .await?;
+
+            let mut tree = FileTree {
+                name,
+                path,
+                ..Default::default()
+            };
+
+            for entry in entries {
+                if !entry.is_folder() {
+                    tree.files.push(entry);
+                    continue;
+                }
 
-        response.url = format!("{}{}{}", self.project_url, STORAGE_V1, response.url);
+                let path = entry.full_path(&tree.path);
+                let folder = match depth_left {
+                    0 => FileTree {
+                        name: entry.name,
+                        path,
+                        truncated: true,
+                        ..Default::default()
+                    },
+                    _ => {
+                        self.list_tree_folder(bucket_id, entry.name, path, depth_left - 1)
+                            .await?
+                    }
+                };
+                tree.folders.push(folder);
+            }
 
-        Ok(response)
+            tree.folders.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok(tree)
+        })
     }
 
-    /// Upload a file to a signed url
+    /// Create an empty folder, by uploading a zero-byte `.emptyFolderPlaceholder` object into it
     ///
-    /// Returns the `url` (without hostname) and authorization `token` upon success
+    /// This matches how the Supabase dashboard creates folders, which otherwise only exist while
+    /// they contain objects.
     ///
     /// # Example
     /// ```rust
-    /// let object = client
-    ///     .upload_to_signed_url("bucket_id", "upload_token", file, "path/to/file.txt", None).await.unwrap();
-    ///```
-    pub async fn upload_to_signed_url(
+    /// client.create_folder("bucket_id", "folder/subfolder").await.unwrap();
+    /// ```
+    pub async fn create_folder(
         &self,
         bucket_id: &str,
-        token: &str,
-        data: Vec<u8>,
-        path: &str,
-        options: Option<FileOptions<'_>>,
-    ) -> Result<UploadToSignedUrlResponse, Error> {
-        let mut headers = self.headers.clone();
-        if !headers.contains_key(AUTHORIZATION) {
-            headers.insert(
-                AUTHORIZATION,
-                HeaderValue::from_str(&format!("Bearer {}", &self.api_key))?,
-            );
-        }
-
-        // Set optional headers
-        if let Some(opts) = options {
-            if let Some(cache_control) = opts.cache_control {
-                headers.insert(
-                    CACHE_CONTROL,
-                    HeaderValue::from_str(&format!("{}", cache_control.as_secs()))?,
-                );
-            }
-
-            if let Some(content_type) = opts.content_type {
-                headers.insert(
-                    CONTENT_TYPE,
-                    HeaderValue::from_str(&format!("{}", content_type))?,
-                );
-            }
-
-            if opts.upsert {
-                headers.insert(
-                    "x-upsert",
-                    HeaderValue::from_str(&format!("{}", opts.upsert))?,
-                );
-            }
-        }
-
-        let res = self
-            .client
-            .put(format!(
-                "{}{}/object/upload/sign/{}/{}?token={}",
-                self.project_url, STORAGE_V1, bucket_id, path, token
-            ))
-            .headers(headers)
-            .body(data)
-            .send()
-            .await?;
-
-        let res_status = res.status();
-        let res_body = res.text().await?;
-
-        let response: UploadToSignedUrlResponse =
-            serde_json::from_str(&res_body).map_err(|_| Error::StorageError {
-                status: res_status,
-                message: res_body,
-            })?;
+        folder_path: &str,
+    ) -> Result<ObjectResponse, Error> {
+        let path = format!(
+            "{}/{}",
+            folder_path.trim_matches('/'),
+            EMPTY_FOLDER_PLACEHOLDER
+        );
 
-        Ok(response)
+        self.upload_file(bucket_id, Vec::new(), &path, None).await
     }
 
-    /// Returns a public URL for accessing an asset in a storage bucket
-    ///
-    /// # Arguments
-    ///
-    /// * `bucket_id` - Unique identifier for the storage bucket
-    /// * `path` - Path to the file within the bucket, formatted as 'folder/subfolder/filename.ext'
-    /// * `options` - Optional parameters for customizing the download URL:
-    ///   - Image transformations (uses `/render/image` endpoint)
-    ///   - Download behavior configurations
+    /// List the full path of every object in a bucket, descending into nested folders
     ///
-    /// # Examples
+    /// Fails with `Error::InvalidArgument` if folders are nested deeper than `MAX_FOLDER_DEPTH`.
     ///
+    /// # Example
     /// ```rust
-    /// // Basic usage
-    /// let url = client.get_public_url("photos", "vacations/beach.jpg", None).await?;
-    ///
-    /// // With image transformation
-    /// let options = DownloadOptions {
-    ///     transform: Some(Transform { width: 300, ..Default::default() }),
-    ///     ..Default::default()
-    /// };
-    /// let url = client.get_public_url("photos", "vacations/beach.jpg", Some(options)).await?;
+    /// let keys = client.walk_bucket("bucket_id").await.unwrap();
     /// ```
+    pub async fn walk_bucket(&self, bucket_id: &str) -> Result<Vec<String>, Error> {
+        self.list_object_paths(bucket_id, "").await
+    }
+
+    /// Collect the full path of every object under `prefix`, descending into folders and paging
+    /// through large listings
     ///
-    /// # Note
-    ///
-    /// The URL can also be manually constructed by combining:
-    /// `{project_url}/storage/v1/object/public/{bucket_id}/{path}`
-    pub async fn get_public_url(
-        &self,
-        bucket_id: &str,
-        path: &str,
-        options: Option<DownloadOptions<'_>>,
-    ) -> Result<String, Error> {
-        let renderpath = match &options {
-            Some(opts) if opts.transform.is_some() => "render/image",
-            _ => "object",
-        };
+    /// Each folder is listed at most once. Folders deeper than `MAX_FOLDER_DEPTH` fail with
+    /// `Error::InvalidArgument` rather than being skipped, so callers never act on a partial list.
+    async fn list_object_paths(&self, bucket_id: &str, prefix: &str) -> Result<Vec<String>, Error> {
+        let mut paths = Vec::new();
+        let mut visited = HashSet::new();
+        let mut folders = vec![(prefix.trim_matches('/').to_string(), 0)];
+
+        while let Some((folder, depth)) = folders.pop() {
+            if depth > MAX_FOLDER_DEPTH {
+                return Err(Error::InvalidArgument {
+                    message: format!(
+                        "folder `{folder}` is nested deeper than MAX_FOLDER_DEPTH ({MAX_FOLDER_DEPTH})"
+                    ),
+                });
+            }
+            if !visited.insert(folder.clone()) {
+                continue;
+            }
 
-        let url_str = format!(
-            "{}{STORAGE_V1}/{renderpath}/public/{bucket_id}/{path}",
-            self.project_url
-        );
+            let mut offset = 0;
+
+            loop {
+                let options = FileSearchOptions {
+                    limit: Some(LIST_PAGE_SIZE),
+                    offset: Some(offset),
+                    include_metadata: Some(false),
+                    ..Default::default()
+                };
+
+                let page = self
+                    .list_files(bucket_id, Some(&folder), Some(options))
+                    .await?;
+                let page_len = page.len();
+
+                for object in page {
+                    let path = object.full_path(&folder);
+
+                    if object.is_folder() {
+                        folders.push((path, depth + 1));
+                    } else {
+                        paths.push(path);
+                    }
+                }
 
-        match options {
-            Some(opts) => build_url_with_options(&url_str, &opts),
-            None => Ok(url_str),
+                if page_len < LIST_PAGE_SIZE as usize {
+                    break;
+                }
+                offset += LIST_PAGE_SIZE;
+            }
         }
+
+        Ok(paths)
     }
 
-    /// Move a file from one path to another
+    /// Copy a file from one path to another
+    ///
+    /// Folders in `to_path` that don't exist yet are created implicitly, since folders are only
+    /// prefixes of object keys.
+    ///
+    /// Without `copy_metadata` the destination may lose the source's content type, use
+    /// `copy_file_with_content_type` to set it explicitly.
+    ///
     /// # Example
     ///
     /// ```rust
-    /// // Copies `3.txt` into `folder/4.txt` within the same bucket
+    /// // Copies `3.txt` into `folder/4.txt` within the same bucket, including metadata
     /// let key = client
-    ///     .move_file("from_bucket", None, "3.txt", Some("folder/4.txt"))
+    ///     .copy_file("from_bucket", None, "3.txt", Some("folder/4.txt"), true)
     ///     .await
     ///     .unwrap();
     ///
-    /// // Copies `a.txt` into `folder/b.txt` in a different bucket
+    /// // Copies `a.txt` into `folder/b.txt` in a different bucket, including metadata
     /// let key = client
-    ///     .move_file("from_bucket", "to_bucket", "a.txt", Some("folder/b.txt"))
+    ///     .copy_file("from_bucket", "to_bucket", "a.txt", Some("folder/b.txt"), true)
     ///     .await
     ///     .unwrap();
     /// ```
-    pub async fn move_file(
+    pub async fn copy_file(
         &self,
         from_bucket: &str,
         to_bucket: Option<&str>,
         from_path: &str,
-        to_path: &str,
+        to_path: Option<&str>,
+        copy_metadata: bool,
     ) -> Result<String, Error> {
-        let mut headers = self.headers.clone();
-        headers.insert(CONTENT_TYPE, HeaderValue::from_str("application/json")?);
-        if !headers.contains_key(AUTHORIZATION) {
-            headers.insert(
-                AUTHORIZATION,
-                HeaderValue::from_str(&format!("Bearer {}", &self.api_key))?,
-            );
-        }
-
-        let payload = MoveFilePayload {
+        let payload = CopyFilePayload {
             bucket_id: from_bucket,
             source_key: from_path,
             destination_bucket: to_bucket.unwrap_or(from_bucket),
-            destination_key: to_path,
+            destination_key: to_path.unwrap_or(from_path),
+            copy_metadata,
+            metadata: None,
         };
 
-        let body = serde_json::to_string(&payload)?;
-
-        let res = self
-            .client
-            .post(format!("{}{}/object/move", self.project_url, STORAGE_V1,))
-            .headers(headers)
-            .body(body)
-            .send()
-            .await?;
-
-        let res_status = res.status();
-        let res_body = res.text().await?;
+        self.copy_object(payload, false).await
+    }
 
-        let value: BucketResponse =
-            serde_json::from_str(&res_body).map_err(|_| Error::StorageError {
-                status: res_status,
-                message: res_body,
-            })?;
+    /// Copy a file from one path to another, serving the destination with `content_type`
+    ///
+    /// When `copy_file` is called with `copy_metadata: false`, the destination gets the server's
+    /// default metadata, which may not keep the source's content type. This sets it explicitly
+    /// instead of copying the source's metadata.
+    ///
+    /// # Example
+    /// ```rust
+    /// let key = client
+    ///     .copy_file_with_content_type("from_bucket", None, "a.png", Some("b.png"), "image/png")
+    ///     .await
+    ///     .unwrap();
+    /// ```
+    pub async fn copy_file_with_content_type(
+        &self,
+        from_bucket: &str,
+        to_bucket: Option<&str>,
+        from_path: &str,
+        to_path: Option<&str>,
+        content_type: &str,
+    ) -> Result<String, Error> {
+        let payload = CopyFilePayload {
+            bucket_id: from_bucket,
+            source_key: from_path,
+            destination_bucket: to_bucket.unwrap_or(from_bucket),
+            destination_key: to_path.unwrap_or(from_path),
+            copy_metadata: false,
+            metadata: Some(ObjectMetadataPayload {
+                mimetype: Some(content_type.to_string()),
+                ..Default::default()
+            }),
+        };
+
+        self.copy_object(payload, false).await
+    }
+
+    /// Copy a file from one path to another, serving the destination with the cache control and
+    /// content type set in `options`
+    ///
+    /// Useful when promoting a draft to a cached public asset. Only `cache_control`,
+    /// `cache_control_header` and `content_type` are used. Setting a cache control without a
+    /// content type keeps the source's content type, which is read with an extra HEAD request.
+    /// Without either, this behaves like `copy_file`.
+    ///
+    /// # Example
+    /// ```rust
+    /// let options = FileOptions {
+    ///     cache_control_header: Some("public, max-age=31536000, immutable"),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let key = client
+    ///     .copy_file_with_options("drafts", Some("public"), "logo.png", None, true, Some(options))
+    ///     .await
+    ///     .unwrap();
+    /// ```
+    pub async fn copy_file_with_options(
+        &self,
+        from_bucket: &str,
+        to_bucket: Option<&str>,
+        from_path: &str,
+        to_path: Option<&str>,
+        copy_metadata: bool,
+        options: Option<FileOptions<'_>>,
+    ) -> Result<String, Error> {
+        let metadata = match options {
+            Some(options) => {
+                self.destination_metadata(from_bucket, from_path, &options)
+                    .await?
+            }
+            None => None,
+        };
+
+        let payload = CopyFilePayload {
+            bucket_id: from_bucket,
+            source_key: from_path,
+            destination_bucket: to_bucket.unwrap_or(from_bucket),
+            destination_key: to_path.unwrap_or(from_path),
+            copy_metadata,
+            metadata,
+        };
+
+        self.copy_object(payload, false).await
+    }
+
+    /// The metadata a copied or moved object should be served with, or `None` to leave it as is
+    ///
+    /// The API replaces both values when either is set, so a missing content type is read from
+    /// the source object.
+    async fn destination_metadata(
+        &self,
+        bucket_id: &str,
+        path: &str,
+        options: &FileOptions<'_>,
+    ) -> Result<Option<ObjectMetadataPayload>, Error> {
+        let cache_control = options.cache_control_value();
+        let mimetype = match (options.content_type, &cache_control) {
+            (Some(content_type), _) => Some(content_type.to_string()),
+            (None, Some(_)) => self
+                .head_existing_object(bucket_id, path)
+                .await?
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string),
+            (None, None) => return Ok(None),
+        };
+
+        Ok(Some(ObjectMetadataPayload {
+            mimetype,
+            cache_control,
+        }))
+    }
+
+    /// Send a copy request, returning the destination key
+    ///
+    /// With `upsert`, an existing destination is replaced, e.g. to copy an object onto itself
+    /// with new metadata.
+    async fn copy_object(
+        &self,
+        payload: CopyFilePayload<'_>,
+        upsert: bool,
+    ) -> Result<String, Error> {
+        let mut headers = self.headers.clone();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_str("application/json")?);
+        if upsert {
+            headers.insert("x-upsert", HeaderValue::from_str("true")?);
+        }
+        if !headers.contains_key(AUTHORIZATION) {
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {}", &self.api_key))?,
+            );
+        }
+
+        let body = serde_json::to_string(&payload)?;
+
+        let res = self
+            .client
+            .post(format!("{}{}/object/copy", self.project_url, STORAGE_V1,))
+            .headers(headers)
+            .body(body)
+            .query(&self.query)
+            .send_with_method(self)
+            .await?;
+
+        let value: CopyFileResponse = parse_response(res)
+            .await
+            .map_err(|err| source_not_found(err, payload.bucket_id, payload.source_key))?;
+
+        Ok(value.key)
+    }
+
+    /// Change the content type of an existing file without re-uploading it, returning the key on
+    /// success
+    ///
+    /// The storage API has no endpoint for editing object metadata, so the file is copied onto
+    /// itself with the new content type.
+    ///
+    /// # Example
+    /// ```rust
+    /// let key = client
+    ///     .update_object_content_type("bucket_id", "images/photo.png", "image/png")
+    ///     .await
+    ///     .unwrap();
+    /// ```
+    pub async fn update_object_content_type(
+        &self,
+        bucket_id: &str,
+        path: &str,
+        content_type: &str,
+    ) -> Result<String, Error> {
+        let metadata = ObjectMetadataPayload {
+            mimetype: Some(content_type.to_string()),
+            ..Default::default()
+        };
+
+        self.update_object_metadata(bucket_id, path, metadata).await
+    }
+
+    /// Copy an object onto itself with new metadata, returning the key
+    async fn update_object_metadata(
+        &self,
+        bucket_id: &str,
+        path: &str,
+        metadata: ObjectMetadataPayload,
+    ) -> Result<String, Error> {
+        let payload = CopyFilePayload {
+            bucket_id,
+            source_key: path,
+            destination_bucket: bucket_id,
+            destination_key: path,
+            copy_metadata: false,
+            metadata: Some(metadata),
+        };
+
+        self.copy_object(payload, true).await
+    }
+
+    /// Create a signed download url, returns a signed_url on success
+    ///
+    /// `expires_in` is in seconds. Fails with `Error::InvalidArgument` if it's zero or longer
+    /// than `with_max_signed_url_expiry` allows. Whitespace around `path` is trimmed, and a blank
+    /// path fails with `Error::InvalidArgument`.
+    ///
+    /// # Example
+    /// ```rust
+    ///
+    /// client
+    ///    .create_signed_url("list_files", "3.txt", 12431234)
+    ///    .await
+    ///    .unwrap();
+    /// ```
+    pub async fn create_signed_url(
+        &self,
+        bucket_id: &str,
+        path: &str,
+        expires_in: u64,
+        options: Option<DownloadOptions<'_>>,
+    ) -> Result<String, Error> {
+        self.check_signed_url_expiry(expires_in)?;
+        let path = trim_url_path(path)?;
+
+        let mut headers = self.headers.clone();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_str("application/json")?);
+        if !headers.contains_key(AUTHORIZATION) {
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {}", &self.api_key))?,
+            );
+        }
+
+        let payload = CreateSignedUrlPayload {
+            expires_in,
+            transform: options.as_ref().and_then(|opts| opts.transform.clone()),
+        };
+
+        let body = serde_json::to_string(&payload)?;
+
+        let res = self
+            .client
+            .post(format!(
+                "{}{}/object/sign/{}/{}",
+                self.project_url, STORAGE_V1, bucket_id, path
+            ))
+            .headers(headers)
+            .body(body)
+            .query(&self.query)
+            .send_with_method(self)
+            .await?;
+
+        let signed_url_response: SignedUrlResponse = parse_response(res).await?;
+
+        let signed_url = format!(
+            "{}{}{}",
+            self.project_url, STORAGE_V1, signed_url_response.signed_url
+        );
+
+        match options {
+            Some(opts) => build_url_with_options(&signed_url, &opts),
+            None => Ok(signed_url),
+        }
+    }
+
+    /// Create a signed download url for an object whose path is given as separate segments
+    ///
+    /// The segments are joined and percent-encoded with `join_path_segments`.
+    ///
+    /// # Example
+    /// ```rust
+    /// let url = client
+    ///     .create_signed_url_from_segments("list_files", ["folder", "3.txt"], 60, None)
+    ///     .await
+    ///     .unwrap();
+    /// ```
+    pub async fn create_signed_url_from_segments<I, S>(
+        &self,
+        bucket_id: &str,
+        segments: I,
+        expires_in: u64,
+        options: Option<DownloadOptions<'_>>,
+    ) -> Result<String, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.create_signed_url(
+            bucket_id,
+            &join_path_segments(segments),
+            expires_in,
+            options,
+        )
+        .await
+    }
+
+    /// Create a signed download url, returning it along with the token it embeds
+    ///
+    /// # Example
+    /// ```rust
+    /// let signed = client
+    ///    .create_signed_url_with_token("list_files", "3.txt", 12431234, None)
+    ///    .await
+    ///    .unwrap();
+    ///
+    /// println!("{} {}", signed.signed_url, signed.token);
+    /// ```
+    pub async fn create_signed_url_with_token(
+        &self,
+        bucket_id: &str,
+        path: &str,
+        expires_in: u64,
+        options: Option<DownloadOptions<'_>>,
+    ) -> Result<SignedUrlWithToken, Error> {
+        let signed_url = self
+            .create_signed_url(bucket_id, path, expires_in, options)
+            .await?;
+        let token = extract_token(&signed_url)?;
+
+        Ok(SignedUrlWithToken { signed_url, token })
+    }
+
+    /// Create multiple signed download urls, returning one `SignedUrlEntry` per path in the same
+    /// order as `paths`
+    ///
+    /// Paths that couldn't be signed, e.g. because the object doesn't exist, have no `signed_url`
+    /// and the reason in `error`, rather than failing the whole request.
+    ///
+    /// The API accepts at most `SIGN_BATCH_SIZE` paths per call, so longer lists are split into
+    /// batches. At most `max_concurrency` batches are sent at once, defaulting to the client's
+    /// `with_max_concurrency` setting.
+    ///
+    /// Whitespace around each path is trimmed, including in the returned entries' `path`, and a
+    /// blank path fails the whole call with `Error::InvalidArgument`.
+    ///
+    /// # Example
+    /// ```rust
+    ///
+    /// let entries = client
+    ///    .create_multiple_signed_urls("bucket_id", vec!["1.txt", "2.txt", "3.txt"], 100_000, None)
+    ///    .await
+    ///    .unwrap();
+    ///
+    /// for entry in entries {
+    ///     match entry.signed_url {
+    ///         Some(url) => println!("{}: {url}", entry.path),
+    ///         None => println!("{}: {:?}", entry.path, entry.error),
+    ///     }
+    /// }
+    /// ```
+    pub async fn create_multiple_signed_urls(
+        &self,
+        bucket_id: &str,
+        paths: Vec<&str>,
+        expires_in: u64,
+        max_concurrency: Option<usize>,
+    ) -> Result<Vec<SignedUrlEntry>, Error> {
+        self.check_signed_url_expiry(expires_in)?;
+        let max_concurrency = max_concurrency.unwrap_or(self.max_concurrency).max(1);
+        let paths = paths
+            .into_iter()
+            .map(trim_url_path)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let batches = paths
+            .chunks(SIGN_BATCH_SIZE)
+            .map(|batch| self.create_signed_urls_batch(bucket_id, batch, expires_in));
+
+        // `buffered` keeps the batches in order, so the entries still line up with `paths`
+        let entries: Vec<Vec<SignedUrlEntry>> = stream::iter(batches)
+            .buffered(max_concurrency)
+            .try_collect()
+            .await?;
+
+        Ok(entries.into_iter().flatten().collect())
+    }
+
+    /// Sign a batch of at most `SIGN_BATCH_SIZE` paths
+    async fn create_signed_urls_batch(
+        &self,
+        bucket_id: &str,
+        paths: &[&str],
+        expires_in: u64,
+    ) -> Result<Vec<SignedUrlEntry>, Error> {
+        let mut headers = self.headers.clone();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_str("application/json")?);
+        if !headers.contains_key(AUTHORIZATION) {
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {}", &self.api_key))?,
+            );
+        }
+
+        let payload = CreateMultipleSignedUrlsPayload {
+            expires_in,
+            paths: paths.to_vec(),
+        };
+
+        let body = serde_json::to_string(&payload)?;
+
+        let res = self
+            .client
+            .post(format!(
+                "{}{}/object/sign/{}",
+                self.project_url, STORAGE_V1, bucket_id
+            ))
+            .headers(headers)
+            .body(body)
+            .query(&self.query)
+            .send_with_method(self)
+            .await?;
+
+        let response: Vec<MultipleSignedUrlResponse> = parse_response(res).await?;
+
+        // Entries are matched up by path, since the API may omit paths it couldn't sign
+        let by_path: HashMap<String, MultipleSignedUrlResponse> = response
+            .into_iter()
+            .filter_map(|entry| Some((entry.path.clone()?, entry)))
+            .collect();
+
+        let entries = paths
+            .iter()
+            .map(|path| {
+                let (signed_url, error) = match by_path.get(*path) {
+                    Some(MultipleSignedUrlResponse {
+                        signed_url: Some(signed_url),
+                        ..
+                    }) => (
+                        Some(format!("{}{}{}", self.project_url, STORAGE_V1, signed_url)),
+                        None,
+                    ),
+                    Some(entry) => (
+                        None,
+                        Some(
+                            entry
+                                .error
+                                .clone()
+                                .unwrap_or_else(|| "Not signed".to_string()),
+                        ),
+                    ),
+                    None => (None, Some("Missing from the response".to_string())),
+                };
+
+                SignedUrlEntry {
+                    path: path.to_string(),
+                    signed_url,
+                    error,
+                }
+            })
+            .collect();
+
+        Ok(entries)
+    }
+
+    /// Create a signed upload url,
+    ///
+    /// Returns the fully-qualified `url` and authorization `token` on success. The url's path is
+    /// normalized to percent-encode the same way as other urls built by the client.
+    ///
+    /// Whitespace around `path` is trimmed, and a blank path fails with `Error::InvalidArgument`.
+    ///
+    /// # Example
+    /// ```rust
+    /// let signed = client.create_signed_upload_url("list_files", "42.txt").await.unwrap();
+    /// ```
+    pub async fn create_signed_upload_url(
+        &self,
+        bucket_id: &str,
+        path: &str,
+    ) -> Result<SignedUploadUrlResponse, Error> {
+        let path = trim_url_path(path)?;
+        let mut headers = self.headers.clone();
+        if !headers.contains_key(AUTHORIZATION) {
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {}", &self.api_key))?,
+            );
+        }
+
+        let res = self
+            .client
+            .post(format!(
+                "{}{}/object/upload/sign/{}/{}",
+                self.project_url, STORAGE_V1, bucket_id, path
+            ))
+            .headers(headers)
+            .query(&self.query)
+            .send_with_method(self)
+            .await?;
+
+        let mut response: SignedUploadUrlResponse = parse_response(res).await?;
+
+        response.url = format!(
+            "{}{}{}",
+            self.project_url,
+            STORAGE_V1,
+            normalize_signed_path(&response.url)?
+        );
+
+        Ok(response)
+    }
+
+    /// Check an upload's content type against the bucket's allowed mime types, when requested
+    /// through `FileOptions::validate_mime_type`
+    ///
+    /// The bucket is only fetched the first time, later uploads use the cached bucket until
+    /// `invalidate_allowed_mime_types` is called.
+    async fn validate_mime_type(
+        &self,
+        bucket_id: &str,
+        opts: &FileOptions<'_>,
+    ) -> Result<(), Error> {
+        let Some(content_type) = opts.content_type.filter(|_| opts.validate_mime_type) else {
+            return Ok(());
+        };
+
+        let cached = self.bucket_cache.lock().unwrap().get(bucket_id).cloned();
+        let bucket = match cached {
+            Some(bucket) => bucket,
+            None => {
+                let bucket = self.get_bucket(bucket_id).await?;
+                self.bucket_cache
+                    .lock()
+                    .unwrap()
+                    .insert(bucket_id.to_string(), bucket.clone());
+                bucket
+            }
+        };
+
+        if bucket.allows_mime_type(content_type) {
+            return Ok(());
+        }
+
+        Err(Error::MimeTypeNotAllowed {
+            mime: content_type.to_string(),
+            allowed: bucket.allowed_mime_types.unwrap_or_default(),
+        })
+    }
+
+    /// Upload a file to a signed url
+    ///
+    /// Returns the `url` (without hostname) and authorization `token` upon success
+    ///
+    /// Whitespace around `path` is trimmed, as in `create_signed_upload_url`, so the upload goes
+    /// to the key the url was signed for.
+    ///
+    /// # Example
+    /// ```rust
+    /// let object = client
+    ///     .upload_to_signed_url("bucket_id", "upload_token", file, "path/to/file.txt", None).await.unwrap();
+    ///```
+    pub async fn upload_to_signed_url(
+        &self,
+        bucket_id: &str,
+        token: &str,
+        data: Vec<u8>,
+        path: &str,
+        options: Option<FileOptions<'_>>,
+    ) -> Result<UploadToSignedUrlResponse, Error> {
+        let streaming = options.as_ref().is_some_and(|opts| opts.duplex.is_some());
+        let body = upload_body(data, streaming);
+
+        self.upload_body_to_signed_url(bucket_id, token, body, path, None, options)
+            .await
+    }
+
+    /// Upload a file to a signed url from a stream of chunks
+    ///
+    /// As with `upload_file_stream`, a known `content_length` is sent as the `Content-Length`
+    /// header and checked against the stream, otherwise the body is sent with chunked transfer
+    /// encoding.
+    ///
+    /// # Example
+    /// ```rust
+    /// let chunks = futures_util::stream::iter(vec![Ok::<_, std::io::Error>(bytes)]);
+    /// let object = client
+    ///     .upload_to_signed_url_stream("bucket_id", "upload_token", chunks, "path/to/file.txt", Some(len), None)
+    ///     .await
+    ///     .unwrap();
+    /// ```
+    pub async fn upload_to_signed_url_stream<S>(
+        &self,
+        bucket_id: &str,
+        token: &str,
+        stream: S,
+        path: &str,
+        content_length: Option<u64>,
+        options: Option<FileOptions<'_>>,
+    ) -> Result<UploadToSignedUrlResponse, Error>
+    where
+        S: TryStream + Send + 'static,
+        S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+        Bytes: From<S::Ok>,
+    {
+        let progress = Arc::new(StreamProgress::default());
+        let result = self
+            .upload_body_to_signed_url(
+                bucket_id,
+                token,
+                length_checked_body(stream, content_length, progress.clone()),
+                path,
+                content_length,
+                options,
+            )
+            .await;
+
+        check_content_length(result, content_length, &progress)
+    }
+
+    async fn upload_body_to_signed_url(
+        &self,
+        bucket_id: &str,
+        token: &str,
+        body: Body,
+        path: &str,
+        content_length: Option<u64>,
+        options: Option<FileOptions<'_>>,
+    ) -> Result<UploadToSignedUrlResponse, Error> {
+        let path = trim_url_path(path)?;
+        let mut headers = self.headers.clone();
+        if !headers.contains_key(AUTHORIZATION) {
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {}", &self.api_key))?,
+            );
+        }
+
+        // Streamed bodies are sent chunked unless the caller knows their length
+        if let Some(content_length) = content_length {
+            headers.insert(CONTENT_LENGTH, HeaderValue::from(content_length));
+        }
+
+        // Set optional headers
+        if let Some(opts) = options {
+            insert_file_option_headers(&mut headers, &opts)?;
+            self.validate_mime_type(bucket_id, &opts).await?;
+        }
+        self.insert_default_cache_control(&mut headers);
+
+        let res = self
+            .client
+            .put(format!(
+                "{}{}/object/upload/sign/{}/{}",
+                self.project_url, STORAGE_V1, bucket_id, path
+            ))
+            .headers(headers)
+            .body(body)
+            .query(&[("token", token)])
+            .query(&self.query)
+            .send_with_method(self)
+            .await?;
+
+        let response: UploadToSignedUrlResponse = parse_response(res).await?;
+
+        Ok(response)
+    }
+
+    /// Returns a public URL for accessing an asset in a storage bucket
+    ///
+    /// # Arguments
+    ///
+    /// * `bucket_id` - Unique identifier for the storage bucket
+    /// * `path` - Path to the file within the bucket, formatted as 'folder/subfolder/filename.ext'.
+    ///   Surrounding whitespace is trimmed, and a blank path fails with `Error::InvalidArgument`
+    /// * `options` - Optional parameters for customizing the download URL:
+    ///   - Image transformations (uses `/render/image` endpoint)
+    ///   - Download behavior configurations
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// // Basic usage
+    /// let url = client.get_public_url("photos", "vacations/beach.jpg", None).await?;
+    ///
+    /// // With image transformation
+    /// let options = DownloadOptions {
+    ///     transform: Some(Transform { width: 300, ..Default::default() }),
+    ///     ..Default::default()
+    /// };
+    /// let url = client.get_public_url("photos", "vacations/beach.jpg", Some(options)).await?;
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// The URL can also be manually constructed by combining:
+    /// `{project_url}/storage/v1/object/public/{bucket_id}/{path}`
+    pub async fn get_public_url(
+        &self,
+        bucket_id: &str,
+        path: &str,
+        options: Option<DownloadOptions<'_>>,
+    ) -> Result<String, Error> {
+        let path = trim_url_path(path)?;
+        let renderpath = match &options {
+            Some(opts) if opts.transform.is_some() => "render/image",
+            _ => "object",
+        };
+
+        let url_str = format!(
+            "{}{STORAGE_V1}/{renderpath}/public/{bucket_id}/{path}",
+            self.project_url
+        );
+
+        match options {
+            Some(opts) => build_url_with_options(&url_str, &opts),
+            None => Ok(url_str),
+        }
+    }
+
+    /// Returns the public URL for an object as a parsed `Url`
+    ///
+    /// Takes the same arguments as `get_public_url`, for callers that want to adjust the URL
+    /// further, e.g. adding query parameters or swapping the host for a CDN.
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut url = client.public_url_parsed("photos", "vacations/beach.jpg", None).await?;
+    /// url.set_host(Some("cdn.example.com")).unwrap();
+    /// ```
+    pub async fn public_url_parsed(
+        &self,
+        bucket_id: &str,
+        path: &str,
+        options: Option<DownloadOptions<'_>>,
+    ) -> Result<Url, Error> {
+        let url = self.get_public_url(bucket_id, path, options).await?;
+
+        Url::parse(&url).map_err(|err| Error::UrlParseError {
+            message: format!("Failed to parse public url {url}: {err}"),
+        })
+    }
+
+    /// Returns the public URL for an object with its ETag appended as a `v` query parameter, so
+    /// browsers and CDNs fetch it again once the object changes
+    ///
+    /// The ETag is looked up with `object_etag`. The URL is returned without `v` if the server
+    /// didn't send one.
+    ///
+    /// # Example
+    /// ```rust
+    /// let url = client.public_url_cache_busted("photos", "logo.png").await?;
+    /// ```
+    pub async fn public_url_cache_busted(
+        &self,
+        bucket_id: &str,
+        path: &str,
+    ) -> Result<String, Error> {
+        let path = trim_url_path(path)?;
+        let etag = self.object_etag(bucket_id, path).await?;
+        let mut url = self.public_url_parsed(bucket_id, path, None).await?;
+
+        if let Some(etag) = etag {
+            url.query_pairs_mut().append_pair("v", &etag);
+        }
+
+        Ok(url.to_string())
+    }
+
+    /// Returns a public URL for an object whose path is given as separate segments
+    ///
+    /// The segments are joined and percent-encoded with `join_path_segments`.
+    ///
+    /// # Example
+    /// ```rust
+    /// let url = client
+    ///     .get_public_url_from_segments("photos", ["vacations", "beach day", "1.jpg"], None)
+    ///     .await?;
+    /// ```
+    pub async fn get_public_url_from_segments<I, S>(
+        &self,
+        bucket_id: &str,
+        segments: I,
+        options: Option<DownloadOptions<'_>>,
+    ) -> Result<String, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.get_public_url(bucket_id, &join_path_segments(segments), options)
+            .await
+    }
+
+    /// Move a file from one path to another
+    ///
+    /// Like `copy_file`, `to_path` defaults to `from_path`, which keeps the same key when moving
+    /// between buckets.
+    ///
+    /// The server moves objects with a copy and a delete, which isn't atomic. Use
+    /// `move_file_verified` to check the result for critical data.
+    ///
+    /// Returns `Outcome::Success` for any 2xx response, whatever the server's message.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// // Moves `3.txt` into `folder/4.txt` within the same bucket
+    /// let outcome = client
+    ///     .move_file("from_bucket", None, "3.txt", Some("folder/4.txt"))
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// // Moves `a.txt` into a different bucket, keeping the same key
+    /// let outcome = client
+    ///     .move_file("from_bucket", Some("to_bucket"), "a.txt", None)
+    ///     .await
+    ///     .unwrap();
+    /// ```
+    pub async fn move_file(
+        &self,
+        from_bucket: &str,
+        to_bucket: Option<&str>,
+        from_path: &str,
+        to_path: Option<&str>,
+    ) -> Result<Outcome, Error> {
+        let mut headers = self.headers.clone();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_str("application/json")?);
+        if !headers.contains_key(AUTHORIZATION) {
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {}", &self.api_key))?,
+            );
+        }
+
+        let payload = MoveFilePayload {
+            bucket_id: from_bucket,
+            source_key: from_path,
+            destination_bucket: to_bucket.unwrap_or(from_bucket),
+            destination_key: to_path.unwrap_or(from_path),
+        };
+
+        let body = serde_json::to_string(&payload)?;
+
+        let res = self
+            .client
+            .post(format!("{}{}/object/move", self.project_url, STORAGE_V1,))
+            .headers(headers)
+            .body(body)
+            .query(&self.query)
+            .send_with_method(self)
+            .await?;
+
+        parse_outcome(res)
+            .await
+            .map_err(|err| source_not_found(err, from_bucket, from_path))
+    }
+
+    /// Move a file like `move_file`, then serve the destination with the cache control and
+    /// content type set in `options`
+    ///
+    /// The move API can't change metadata, so it's updated with a follow-up copy of the
+    /// destination onto itself, as `copy_file_with_options` describes. If that update fails,
+    /// the file has still been moved.
+    ///
+    /// # Example
+    /// ```rust
+    /// let options = FileOptions {
+    ///     cache_control: Some(Duration::from_secs(31_536_000)),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let outcome = client
+    ///     .move_file_with_options("drafts", Some("public"), "logo.png", None, Some(options))
+    ///     .await
+    ///     .unwrap();
+    /// ```
+    pub async fn move_file_with_options(
+        &self,
+        from_bucket: &str,
+        to_bucket: Option<&str>,
+        from_path: &str,
+        to_path: Option<&str>,
+        options: Option<FileOptions<'_>>,
+    ) -> Result<Outcome, Error> {
+        let outcome = self
+            .move_file(from_bucket, to_bucket, from_path, to_path)
+            .await?;
+
+        let to_bucket = to_bucket.unwrap_or(from_bucket);
+        let to_path = to_path.unwrap_or(from_path);
+        if let Some(options) = options {
+            if let Some(metadata) = self
+                .destination_metadata(to_bucket, to_path, &options)
+                .await?
+            {
+                self.update_object_metadata(to_bucket, to_path, metadata)
+                    .await?;
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    /// Move a file like `move_file`, then verify the destination exists and the source is gone
+    ///
+    /// Fails with `Error::VerificationFailed` if either check doesn't hold, e.g. because the move
+    /// was interrupted between its copy and delete.
+    ///
+    /// # Example
+    /// ```rust
+    /// let outcome = client
+    ///     .move_file_verified("from_bucket", None, "3.txt", Some("folder/4.txt"))
+    ///     .await
+    ///     .unwrap();
+    /// ```
+    pub async fn move_file_verified(
+        &self,
+        from_bucket: &str,
+        to_bucket: Option<&str>,
+        from_path: &str,
+        to_path: Option<&str>,
+    ) -> Result<Outcome, Error> {
+        let outcome = self
+            .move_file(from_bucket, to_bucket, from_path, to_path)
+            .await?;
+
+        let to_bucket = to_bucket.unwrap_or(from_bucket);
+        let to_path = to_path.unwrap_or(from_path);
+
+        if !self.exists(to_bucket, to_path).await? {
+            return Err(Error::VerificationFailed {
+                message: format!("Moved object is missing from {to_bucket}/{to_path}"),
+            });
+        }
+
+        // Moving an object onto itself leaves it in place
+        if (from_bucket, from_path) != (to_bucket, to_path)
+            && self.exists(from_bucket, from_path).await?
+        {
+            return Err(Error::VerificationFailed {
+                message: format!("Moved object is still present at {from_bucket}/{from_path}"),
+            });
+        }
+
+        Ok(outcome)
+    }
+
+    /// Check whether an object exists, without downloading it
+    ///
+    /// A `400` to the HEAD request is only taken to mean the object is missing once the object's
+    /// info confirms it, any other rejection, e.g. an invalid key, is returned as an error.
+    ///
+    /// # Example
+    /// ```rust
+    /// let exists = client.exists("bucket_id", "path/to/file.txt").await.unwrap();
+    /// ```
+    pub async fn exists(&self, bucket_id: &str, path: &str) -> Result<bool, Error> {
+        let res = self.head_object(bucket_id, path).await?;
+
+        match res.status() {
+            status if status.is_success() => Ok(true),
+            StatusCode::NOT_FOUND => Ok(false),
+            StatusCode::BAD_REQUEST => match self.check_object_info(bucket_id, path).await {
+                Ok(()) => Ok(true),
+                Err(Error::NotFound { .. }) => Ok(false),
+                Err(err) => Err(err),
+            },
+            _ => Err(response_error(read_response(res).await?)),
+        }
+    }
+
+    /// The object's ETag without its surrounding quotes, e.g. to use as a cache key
+    ///
+    /// Returns `None` if the server didn't send an ETag, and `Error::NotFound` if the object
+    /// doesn't exist.
+    ///
+    /// # Example
+    /// ```rust
+    /// let etag = client.object_etag("bucket_id", "path/to/file.txt").await.unwrap();
+    /// ```
+    pub async fn object_etag(&self, bucket_id: &str, path: &str) -> Result<Option<String>, Error> {
+        let res = self.head_existing_object(bucket_id, path).await?;
+
+        let etag = res
+            .headers()
+            .get(ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(|etag| etag.trim_start_matches("W/").trim_matches('"').to_string())
+            .filter(|etag| !etag.is_empty());
+
+        Ok(etag)
+    }
+
+    /// Whether the server accepts byte ranges for the object, i.e. whether it sends
+    /// `Accept-Ranges: bytes`
+    ///
+    /// Use it to decide between resuming a download with a `Range` request and downloading the
+    /// whole object again. Returns `Error::NotFound` if the object doesn't exist.
+    ///
+    /// # Example
+    /// ```rust
+    /// let resumable = client.supports_ranges("bucket_id", "path/to/file.zip").await.unwrap();
+    /// ```
+    pub async fn supports_ranges(&self, bucket_id: &str, path: &str) -> Result<bool, Error> {
+        let res = self.head_existing_object(bucket_id, path).await?;
+
+        let supported = res
+            .headers()
+            .get_all(ACCEPT_RANGES)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .any(|unit| unit.trim().eq_ignore_ascii_case("bytes"));
+
+        Ok(supported)
+    }
+
+    /// Send a HEAD request for an object, mapping a missing object to `Error::NotFound`
+    async fn head_existing_object(&self, bucket_id: &str, path: &str) -> Result<Response, Error> {
+        let res = self.head_object(bucket_id, path).await?;
+
+        match res.status() {
+            status if status.is_success() => Ok(res),
+            StatusCode::NOT_FOUND => Err(Error::NotFound {
+                message: format!("Object not found: {bucket_id}/{path}"),
+            }),
+            StatusCode::BAD_REQUEST => {
+                self.check_object_info(bucket_id, path).await?;
+                Err(response_error(read_response(res).await?))
+            }
+            _ => Err(response_error(read_response(res).await?)),
+        }
+    }
+
+    /// Fetch an object's info to learn why a HEAD request for it failed with a `400`
+    ///
+    /// HEAD responses have no body to carry the real status, so a missing object is reported as
+    /// a plain `400`, just like an invalid key or token. The info endpoint reports the same
+    /// errors with a body, which is mapped to `Error::NotFound` for a missing object.
+    async fn check_object_info(&self, bucket_id: &str, path: &str) -> Result<(), Error> {
+        let mut headers = self.headers.clone();
+        if !headers.contains_key(AUTHORIZATION) {
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {}", &self.api_key))?,
+            );
+        }
+
+        let res = self
+            .client
+            .get(format!(
+                "{}{}/object/info/{}/{}",
+                self.project_url, STORAGE_V1, bucket_id, path
+            ))
+            .headers(headers)
+            .query(&self.query)
+            .send_with_method(self)
+            .await?;
+
+        let parts = read_response(res).await?;
+
+        if parts.status.is_success() {
+            return Ok(());
+        }
+
+        match source_not_found(response_error(parts), bucket_id, path) {
+            Error::NotFound { .. } => Err(Error::NotFound {
+                message: format!("Object not found: {bucket_id}/{path}"),
+            }),
+            err => Err(err),
+        }
+    }
+
+    /// Send a HEAD request for an object, leaving the response status to the caller
+    async fn head_object(&self, bucket_id: &str, path: &str) -> Result<Response, Error> {
+        let mut headers = self.headers.clone();
+        if !headers.contains_key(AUTHORIZATION) {
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {}", &self.api_key))?,
+            );
+        }
+
+        let res = self
+            .client
+            .head(format!(
+                "{}{}/object/{}/{}",
+                self.project_url, STORAGE_V1, bucket_id, path
+            ))
+            .headers(headers)
+            .query(&self.query)
+            .send_with_method(self)
+            .await?;
+
+        Ok(res)
+    }
+}
+
+impl StorageClientBuilder {
+    /// Use an existing `reqwest::Client`, e.g. one configured with timeouts or a proxy
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Send a header with every request
+    ///
+    /// An invalid header value is reported by `build`.
+    pub fn header(
+        mut self,
+        header_name: impl IntoHeaderName,
+        header_value: impl AsRef<str>,
+    ) -> Self {
+        match HeaderValue::from_str(header_value.as_ref()) {
+            Ok(value) => {
+                self.headers.insert(header_name, value);
+            }
+            Err(err) => {
+                self.error.get_or_insert(err.into());
+            }
+        }
+        self
+    }
+
+    /// Send a custom `User-Agent` header, see `StorageClient::with_user_agent`
+    ///
+    /// An invalid value is reported by `build`.
+    pub fn user_agent(self, user_agent: &str) -> Self {
+        self.header(USER_AGENT, user_agent)
+    }
+
+    /// Append a query parameter to every request, see `StorageClient::with_query`
+    pub fn query(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.query.push((name.into(), value.into()));
+        self
+    }
+
+    /// Set the most requests a batch helper sends at once, see
+    /// `StorageClient::with_max_concurrency`
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    /// Retry idempotent uploads, see `StorageClient::with_max_retries`
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Whether to send the `apikey` header, see `StorageClient::with_send_api_key_header`
+    pub fn send_api_key_header(mut self, send_api_key_header: bool) -> Self {
+        self.send_api_key_header = send_api_key_header;
+        self
+    }
+
+    /// Set the longest signed URL expiry, see `StorageClient::with_max_signed_url_expiry`
+    pub fn max_signed_url_expiry(mut self, max_signed_url_expiry: u64) -> Self {
+        self.max_signed_url_expiry = max_signed_url_expiry;
+        self
+    }
+
+    /// Send a Cache-Control header with uploads that don't set their own, see
+    /// `StorageClient::with_default_cache_control`
+    ///
+    /// An invalid value is reported by `build`.
+    pub fn default_cache_control(mut self, cache_control: &str) -> Self {
+        match HeaderValue::from_str(cache_control) {
+            Ok(value) => self.default_cache_control = Some(value),
+            Err(err) => {
+                self.error.get_or_insert(err.into());
+            }
+        }
+        self
+    }
+
+    /// Route every request through the proxy at `proxy_url`, see `StorageClient::with_proxy`
+    ///
+    /// An unparseable proxy url is reported by `build`. Ignored when `http_client` is set,
+    /// configure the proxy on that client instead.
+    pub fn proxy(mut self, proxy_url: &str) -> Self {
+        match parse_proxy(proxy_url) {
+            Ok(proxy) => self.proxy = Some(proxy),
+            Err(err) => {
+                self.error.get_or_insert(err);
+            }
+        }
+        self
+    }
 
-        Ok(value.message)
+    /// Build the StorageClient, failing if any header or proxy url passed to the builder was
+    /// invalid
+    pub fn build(self) -> Result<StorageClient, Error> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+
+        let client = match (self.client, self.proxy) {
+            (Some(client), _) => client,
+            (None, Some(proxy)) => http_client_builder().proxy(proxy).build()?,
+            (None, None) => default_http_client(),
+        };
+
+        Ok(StorageClient {
+            client,
+            project_url: normalize_project_url(self.project_url),
+            api_key: self.api_key,
+            headers: self.headers,
+            max_concurrency: self.max_concurrency,
+            query: self.query,
+            max_retries: self.max_retries,
+            default_cache_control: self.default_cache_control,
+            send_api_key_header: self.send_api_key_header,
+            max_signed_url_expiry: self.max_signed_url_expiry,
+            bucket_cache: Default::default(),
+            request_middleware: None,
+            #[cfg(feature = "middleware")]
+            middleware_client: None,
+        })
+    }
+}
+
+/// A `reqwest::ClientBuilder` for the clients this crate creates, sending `DEFAULT_USER_AGENT`
+fn http_client_builder() -> reqwest::ClientBuilder {
+    reqwest::Client::builder().user_agent(DEFAULT_USER_AGENT)
+}
+
+/// The `reqwest::Client` used unless the caller passes their own
+///
+/// Like `reqwest::Client::new`, this panics if the TLS backend can't be initialized.
+fn default_http_client() -> reqwest::Client {
+    http_client_builder()
+        .build()
+        .expect("failed to build the default reqwest::Client")
+}
+
+/// Parses a proxy url for all requests
+fn parse_proxy(proxy_url: &str) -> Result<Proxy, Error> {
+    Proxy::all(proxy_url).map_err(|err| Error::UrlParseError {
+        message: format!("Invalid proxy url {proxy_url}: {err}"),
+    })
+}
+
+/// Strips trailing slashes from a project url, so joining it with `STORAGE_V1` can't produce `//`
+///
+/// A url without a scheme, like `<project ref>.supabase.co`, gets `https://` prepended, since
+/// reqwest would otherwise reject every request with an unhelpful error. Local instances served
+/// over plain http need the `http://` spelled out.
+fn normalize_project_url(project_url: impl AsRef<str>) -> String {
+    let project_url = project_url.as_ref().trim().trim_end_matches('/');
+
+    let has_scheme = project_url.split_once("://").is_some_and(|(scheme, _)| {
+        !scheme.is_empty()
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    });
+
+    match has_scheme {
+        true => project_url.to_string(),
+        false => format!("https://{project_url}"),
     }
 }
 
+/// Appends the transform and download options to a URL as query parameters
+///
+/// This is shared by `download_file`, `create_signed_url` and `get_public_url` so the three
+/// always serialize `TransformOptions` the same way.
 pub fn build_url_with_options(url_str: &str, options: &DownloadOptions) -> Result<String, Error> {
     let mut url = Url::parse(url_str).map_err(|_| Error::UrlParseError {
         message: "Failed to parse Url".to_string(),
@@ -1058,7 +3147,7 @@ pub fn build_url_with_options(url_str: &str, options: &DownloadOptions) -> Resul
 
         if let Some(resize) = transform.resize {
             match resize {
-                "conver" | "contain" | "fill" => {
+                "cover" | "contain" | "fill" => {
                     query_pairs.append_pair("resize", resize);
                 }
                 _ => {} // Invalid resize option, ignore
@@ -1074,14 +3163,690 @@ pub fn build_url_with_options(url_str: &str, options: &DownloadOptions) -> Resul
     // Release the mutable borrow before generating the final URL
     drop(query_pairs);
 
+    // Don't leave a dangling `?` when there were no options to add
+    if url.query() == Some("") {
+        url.set_query(None);
+    }
+
     Ok(url.to_string())
 }
 
-pub fn extract_token(url: &str) -> Result<&str, Error> {
-    url.split('?')
-        .nth(1)
-        .and_then(|query| query.split('&').find(|param| param.starts_with("token=")))
-        .and_then(|token_param| token_param.strip_prefix("token="))
+/// Runs `future` to completion unless `token` is cancelled first, which drops the in-flight
+/// request
+#[cfg(feature = "cancellation")]
+async fn run_cancellable<T>(
+    token: CancellationToken,
+    future: impl std::future::Future<Output = Result<T, Error>>,
+) -> Result<T, Error> {
+    tokio::select! {
+        _ = token.cancelled() => Err(Error::Cancelled),
+        result = future => result,
+    }
+}
+
+/// The error type of the streams behind upload bodies
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Builds the body of an upload, streaming it in chunks when `streaming` is set
+///
+/// Streamed bodies are sent with chunked transfer encoding rather than a `Content-Length`. The
+/// chunks are slices of `data`, so it isn't copied.
+fn upload_body(data: Vec<u8>, streaming: bool) -> Body {
+    if !streaming {
+        return Body::from(data);
+    }
+
+    let data = Bytes::from(data);
+    let len = data.len();
+    let chunks = (0..len).step_by(UPLOAD_CHUNK_SIZE).map(move |start| {
+        Ok::<_, std::io::Error>(data.slice(start..(start + UPLOAD_CHUNK_SIZE).min(len)))
+    });
+
+    Body::wrap_stream(stream::iter(chunks))
+}
+
+/// How much of a streamed upload's body was read, shared between the body and the request
+/// that sends it
+#[derive(Debug, Default)]
+struct StreamProgress {
+    /// The bytes read from the stream so far
+    sent: AtomicU64,
+    /// Whether the stream ended
+    finished: AtomicBool,
+    /// Whether the body failed because the stream overran or fell short of its declared length
+    mismatched: AtomicBool,
+}
+
+/// Builds the body of a streamed upload, recording how much of `stream` was read in `progress`
+///
+/// With a declared `content_length` the body fails as soon as the stream overruns it, or ends
+/// short of it, aborting the request. `check_content_length` then reports the mismatch.
+fn length_checked_body<S>(
+    stream: S,
+    content_length: Option<u64>,
+    progress: Arc<StreamProgress>,
+) -> Body
+where
+    S: TryStream + Send + 'static,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    Bytes: From<S::Ok>,
+{
+    match content_length {
+        Some(declared) => {
+            let chunks = stream.into_stream().map(|chunk| match chunk {
+                Ok(chunk) => Ok(Bytes::from(chunk)),
+                Err(err) => Err(err.into()),
+            });
+            length_checked_stream(chunks, declared, progress)
+        }
+        None => Body::wrap_stream(stream),
+    }
+}
+
+/// Wraps `chunks` in a body that fails once they overrun `declared` bytes or end short of it
+///
+/// Kept apart from `length_checked_body`, whose `Bytes: From<S::Ok>` bound would otherwise
+/// confuse inference of `Body::wrap_stream`.
+fn length_checked_stream(
+    chunks: impl Stream<Item = Result<Bytes, BoxError>> + Send + 'static,
+    declared: u64,
+    progress: Arc<StreamProgress>,
+) -> Body {
+    let counter = progress.clone();
+    let checked = chunks.map(move |chunk| -> Result<Bytes, BoxError> {
+        let chunk = chunk?;
+        let len = chunk.len() as u64;
+        let actual = counter.sent.fetch_add(len, Ordering::Relaxed) + len;
+        if actual > declared {
+            counter.mismatched.store(true, Ordering::Relaxed);
+            return Err(Error::ContentLengthMismatch { declared, actual }.into());
+        }
+        Ok(chunk)
+    });
+
+    let end = stream::once(async move {
+        progress.finished.store(true, Ordering::Relaxed);
+        let actual = progress.sent.load(Ordering::Relaxed);
+        if actual < declared {
+            progress.mismatched.store(true, Ordering::Relaxed);
+            return Some(Err::<Bytes, BoxError>(
+                Error::ContentLengthMismatch { declared, actual }.into(),
+            ));
+        }
+        None
+    })
+    .filter_map(std::future::ready);
+
+    Body::wrap_stream(checked.chain(end))
+}
+
+/// Reports `Error::ContentLengthMismatch` for a streamed upload whose body failed because the
+/// stream didn't match the declared `content_length`, or that succeeded despite a mismatch
+///
+/// Any other outcome is returned unchanged, so errors raised before the body was read, like a
+/// rejected argument or a `409` from the server, aren't masked.
+fn check_content_length<T>(
+    result: Result<T, Error>,
+    content_length: Option<u64>,
+    progress: &StreamProgress,
+) -> Result<T, Error> {
+    let Some(declared) = content_length else {
+        return result;
+    };
+    let actual = progress.sent.load(Ordering::Relaxed);
+    let mismatched = progress.mismatched.load(Ordering::Relaxed)
+        || (result.is_ok() && progress.finished.load(Ordering::Relaxed) && actual != declared);
+
+    match mismatched {
+        true => Err(Error::ContentLengthMismatch { declared, actual }),
+        false => result,
+    }
+}
+
+/// Inserts the headers described by an upload's `FileOptions`
+///
+/// Each header replaces any value of the same name in `headers`, so a per-call content type
+/// wins over one set for every request with `insert_header`, rather than being sent twice.
+///
+/// `x-upsert` is only sent when upserting, since the API already defaults to rejecting
+/// existing files.
+fn insert_file_option_headers(headers: &mut HeaderMap, opts: &FileOptions) -> Result<(), Error> {
+    if let Some(cache_control) = opts.cache_control_value() {
+        headers.insert(CACHE_CONTROL, HeaderValue::from_str(&cache_control)?);
+    }
+
+    if let Some(content_type) = opts.content_type {
+        if content_type.trim().is_empty() {
+            return Err(Error::InvalidArgument {
+                message: "content_type must not be empty".to_string(),
+            });
+        }
+        headers.insert(CONTENT_TYPE, HeaderValue::from_str(content_type)?);
+    }
+
+    if opts.upsert {
+        headers.insert("x-upsert", HeaderValue::from_static("true"));
+    }
+
+    if opts.create_only {
+        if opts.upsert {
+            return Err(Error::InvalidArgument {
+                message: "create_only can't be combined with upsert".to_string(),
+            });
+        }
+        headers.insert(IF_NONE_MATCH, HeaderValue::from_static("*"));
+    }
+
+    if let Some(content_disposition) = opts.content_disposition {
+        headers.insert(
+            CONTENT_DISPOSITION,
+            HeaderValue::from_str(content_disposition)?,
+        );
+    }
+
+    // The storage API expects custom metadata as base64 encoded JSON
+    if let Some(metadata) = &opts.metadata {
+        let metadata = STANDARD.encode(serde_json::to_string(metadata)?);
+        headers.insert("x-metadata", HeaderValue::from_str(&metadata)?);
+    }
+
+    Ok(())
+}
+
+/// Converts a bucket's allowed `MimeType`s to their string representations, rejecting empty ones
+fn mime_type_strings<'a>(
+    allowed_mime_types: Option<impl IntoIterator<Item = impl Into<MimeType<'a>>>>,
+) -> Result<Option<Vec<String>>, Error> {
+    allowed_mime_types
+        .map(|types| {
+            types
+                .into_iter()
+                .map(|mime| match mime.into().to_string() {
+                    mime if mime.trim().is_empty() => Err(Error::InvalidArgument {
+                        message: "allowed mime types must not be empty".to_string(),
+                    }),
+                    mime => Ok(mime),
+                })
+                .collect()
+        })
+        .transpose()
+}
+
+/// Normalizes a signed url returned by the API to a path relative to `STORAGE_V1`
+///
+/// Depending on the API version the url may be missing its leading slash, have its slashes
+/// percent-encoded, or include the host and `STORAGE_V1` prefix, which are all removed here.
+fn normalize_signed_path(url: &str) -> Result<String, Error> {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let path = match path.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("", |start| &rest[start..]),
+        None => path,
+    };
+
+    let decoded = percent_decode(path);
+    let decoded = format!("/{}", decoded.trim_start_matches('/'));
+    let decoded = decoded.strip_prefix(STORAGE_V1).unwrap_or(&decoded);
+
+    // Re-encode the path through `Url` so it matches the urls built everywhere else
+    let mut normalized = Url::parse("http://localhost").map_err(|_| Error::UrlParseError {
+        message: "Failed to parse Url".to_string(),
+    })?;
+    normalized.set_path(decoded);
+
+    Ok(match query {
+        "" => normalized.path().to_string(),
+        query => format!("{}?{query}", normalized.path()),
+    })
+}
+
+/// Decodes `%XX` escapes, leaving malformed escapes untouched
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match (bytes[i], escaped) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Maps a `404` from copying or moving an object to `Error::NotFound`, naming the source object
+///
+/// The storage API reports a missing source as a 400 with the real status in the body.
+fn source_not_found(error: Error, bucket_id: &str, path: &str) -> Error {
+    match error {
+        Error::StorageError {
+            status, message, ..
+        } if status == StatusCode::NOT_FOUND
+            || serde_json::from_str::<StorageErrorBody>(&message)
+                .ok()
+                .and_then(|error_body| error_body.status_code())
+                == Some(StatusCode::NOT_FOUND) =>
+        {
+            Error::NotFound {
+                message: format!("Source object not found: {bucket_id}/{path}"),
+            }
+        }
+        error => error,
+    }
+}
+
+/// Whether looking up a bucket failed because it doesn't exist
+///
+/// The API reports a missing bucket as a `400` with a `404` status code in the body, or with the
+/// `Bucket not found` error on older versions.
+fn is_bucket_not_found(error: &Error) -> bool {
+    match error {
+        Error::StorageError {
+            status, message, ..
+        } => {
+            *status == StatusCode::NOT_FOUND
+                || serde_json::from_str::<StorageErrorBody>(message).is_ok_and(|error_body| {
+                    error_body.status_code() == Some(StatusCode::NOT_FOUND)
+                        || error_body.error.as_deref() == Some("Bucket not found")
+                })
+        }
+        _ => false,
+    }
+}
+
+/// Maps an upload rejected because the object already exists to `Error::AlreadyExists`
+///
+/// The API reports duplicates as a `400` with a `409` status code in the body, or with a `412`
+/// when `If-None-Match: *` failed.
+fn object_already_exists(error: Error, bucket_id: &str, path: &str) -> Error {
+    let duplicate = match &error {
+        Error::StorageError {
+            status, message, ..
+        } => {
+            matches!(
+                *status,
+                StatusCode::CONFLICT | StatusCode::PRECONDITION_FAILED
+            ) || serde_json::from_str::<StorageErrorBody>(message)
+                .is_ok_and(|error_body| error_body.is_duplicate())
+        }
+        Error::UnexpectedResponse { status, .. } => *status == StatusCode::PRECONDITION_FAILED,
+        _ => false,
+    };
+
+    match duplicate {
+        true => Error::AlreadyExists {
+            name: format!("{bucket_id}/{path}"),
+        },
+        false => error,
+    }
+}
+
+/// Maps a request body rejected by the API's schema validation to `Error::Validation`, so each
+/// invalid field can be reported, e.g. next to a form input
+fn validation_error(error: Error) -> Error {
+    let field_errors = match &error {
+        Error::StorageError { message, .. } => serde_json::from_str::<StorageErrorBody>(message)
+            .map(|error_body| error_body.field_errors())
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+
+    match field_errors.is_empty() {
+        true => error,
+        false => Error::Validation { field_errors },
+    }
+}
+
+/// The parts of a response needed to parse it, or to report it as an error
+struct ResponseParts {
+    status: StatusCode,
+    content_type: Option<String>,
+    body: String,
+    method: Method,
+    /// The URL path, without the query string so tokens aren't leaked into errors
+    path: String,
+}
+
+/// Sends a request through the client's request middleware, recording its method on the response
+/// so errors can name the operation
+trait SendWithMethod {
+    fn send_with_method(
+        self,
+        storage: &StorageClient,
+    ) -> impl Future<Output = Result<Response, Error>> + Send;
+}
+
+impl SendWithMethod for RequestBuilder {
+    fn send_with_method(
+        self,
+        storage: &StorageClient,
+    ) -> impl Future<Output = Result<Response, Error>> + Send {
+        let builder = match &storage.request_middleware {
+            Some(middleware) => middleware(self),
+            None => self,
+        };
+        #[cfg(feature = "middleware")]
+        let middleware_client = storage.middleware_client.clone();
+
+        async move {
+            let (client, request) = builder.build_split();
+            let request = request?;
+            let method = request.method().clone();
+
+            #[cfg(feature = "middleware")]
+            let mut res = match middleware_client {
+                Some(middleware_client) => {
+                    middleware_client
+                        .execute(request)
+                        .await
+                        .map_err(|err| match err {
+                            reqwest_middleware::Error::Reqwest(err) => Error::RequestError(err),
+                            reqwest_middleware::Error::Middleware(err) => Error::MiddlewareError {
+                                message: format!("{err:#}"),
+                            },
+                        })?
+                }
+                None => client.execute(request).await?,
+            };
+            #[cfg(not(feature = "middleware"))]
+            let mut res = client.execute(request).await?;
+
+            res.extensions_mut().insert(method);
+
+            Ok(res)
+        }
+    }
+}
+
+/// Sends a request, sending it again up to `retries` times after a timeout, connection failure
+/// or retryable status
+///
+/// Requests whose body can't be cloned, i.e. streamed uploads, are only sent once.
+async fn send_with_retries(
+    storage: &StorageClient,
+    request: RequestBuilder,
+    retries: u32,
+) -> Result<Response, Error> {
+    for _ in 0..retries {
+        let Some(attempt) = request.try_clone() else {
+            break;
+        };
+
+        match attempt.send_with_method(storage).await {
+            Ok(res) if !is_retryable_status(res.status()) => return Ok(res),
+            Ok(_) => {}
+            Err(Error::RequestError(err)) if err.is_timeout() || err.is_connect() => {}
+            Err(err) => return Err(err),
+        }
+    }
+
+    request.send_with_method(storage).await
+}
+
+/// Whether a response status signals a transient failure worth retrying
+fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Reads a successful download into its body and the headers describing it
+async fn download_response(res: Response) -> Result<DownloadResponse, Error> {
+    let header_value = |name| {
+        res.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(String::from)
+    };
+    let content_type = header_value(CONTENT_TYPE);
+    let content_encoding = header_value(CONTENT_ENCODING);
+    let etag = header_value(ETAG);
+    let last_modified = header_value(LAST_MODIFIED);
+
+    Ok(DownloadResponse {
+        data: res.bytes().await?.into(),
+        content_type,
+        content_encoding,
+        etag,
+        last_modified,
+    })
+}
+
+/// Reads a response into its status, `Content-Type`, body and the request it answered
+async fn read_response(res: Response) -> Result<ResponseParts, Error> {
+    let status = res.status();
+    let content_type = res
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+    let method = res
+        .extensions()
+        .get::<Method>()
+        .cloned()
+        .unwrap_or_default();
+    let path = res.url().path().to_string();
+    let body = res.text().await?;
+
+    Ok(ResponseParts {
+        status,
+        content_type,
+        body,
+        method,
+        path,
+    })
+}
+
+/// Deserializes the JSON body of a successful response
+async fn parse_response<T: DeserializeOwned>(res: Response) -> Result<T, Error> {
+    let parts = read_response(res).await?;
+
+    if !parts.status.is_success() {
+        return Err(response_error(parts));
+    }
+
+    serde_json::from_str(&parts.body).map_err(|_| response_error(parts))
+}
+
+/// Parses the body of a mutating call into a `BucketResponse`
+///
+/// Some API versions respond to a successful mutation with an empty body or `{}`, so a 2xx
+/// without a message is treated as success rather than a deserialization failure.
+async fn parse_bucket_response(res: Response) -> Result<BucketResponse, Error> {
+    let parts = read_response(res).await?;
+
+    if !parts.status.is_success() {
+        return Err(response_error(parts));
+    }
+
+    let is_empty = match parts.body.trim() {
+        "" => true,
+        trimmed => matches!(
+            serde_json::from_str::<serde_json::Value>(trimmed),
+            Ok(serde_json::Value::Object(map)) if map.is_empty()
+        ),
+    };
+
+    if is_empty {
+        return Ok(BucketResponse {
+            message: DEFAULT_SUCCESS_MESSAGE.to_string(),
+        });
+    }
+
+    serde_json::from_str(&parts.body).map_err(|_| response_error(parts))
+}
+
+/// Parses the response of a mutation confirmed with a message into an `Outcome`
+///
+/// Any 2xx is a success. The message is kept when the body has one, but a missing or
+/// unexpected body isn't an error, as its shape varies across API versions.
+async fn parse_outcome(res: Response) -> Result<Outcome, Error> {
+    let parts = read_response(res).await?;
+
+    if !parts.status.is_success() {
+        return Err(response_error(parts));
+    }
+
+    let message = serde_json::from_str::<BucketResponse>(&parts.body)
+        .ok()
+        .map(|response| response.message);
+
+    Ok(Outcome::Success { message })
+}
+
+/// Builds the error for a response that failed, or couldn't be deserialized
+///
+/// Bodies that aren't JSON didn't come from the storage API (e.g. an HTML error page from a
+/// gateway), so they're reported as `UnexpectedResponse` instead of `StorageError`.
+///
+/// The storage API sometimes responds with a `400` and the real status in the body's
+/// `statusCode`, so that takes precedence when classifying the error.
+fn response_error(parts: ResponseParts) -> Error {
+    let ResponseParts {
+        status,
+        content_type,
+        body,
+        method,
+        path,
+    } = parts;
+    let error_body = serde_json::from_str::<StorageErrorBody>(&body).ok();
+    let is_json = content_type
+        .as_deref()
+        .is_some_and(|content_type| content_type.contains("json"))
+        || serde_json::from_str::<serde_json::Value>(&body).is_ok();
+
+    let error_status = error_body
+        .as_ref()
+        .and_then(StorageErrorBody::status_code)
+        .unwrap_or(status);
+    let message = || {
+        error_body
+            .as_ref()
+            .and_then(|error_body| error_body.message.clone())
+            .unwrap_or_else(|| body.clone())
+    };
+
+    if error_body
+        .as_ref()
+        .is_some_and(StorageErrorBody::is_transformation_unavailable)
+    {
+        return Error::FeatureNotAvailable {
+            feature: "image transformations".to_string(),
+            message: message(),
+        };
+    }
+
+    match error_status {
+        StatusCode::UNAUTHORIZED => return Error::Unauthorized { message: message() },
+        StatusCode::FORBIDDEN => return Error::Forbidden { message: message() },
+        _ => {}
+    }
+
+    if !is_json {
+        return Error::UnexpectedResponse {
+            status,
+            content_type,
+            body_snippet: body.chars().take(BODY_SNIPPET_LEN).collect(),
+        };
+    }
+
+    Error::StorageError {
+        status,
+        message: body,
+        method,
+        path,
+    }
+}
+
+/// Joins path segments into an object path, percent-encoding each segment
+///
+/// Leading, trailing and repeated slashes are dropped, so `["a/", "/b c", "d.txt"]` becomes
+/// `a/b%20c/d.txt`.
+pub fn join_path_segments<I, S>(segments: I) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    segments
+        .into_iter()
+        .flat_map(|segment| {
+            segment
+                .as_ref()
+                .split('/')
+                .filter(|part| !part.is_empty())
+                .map(encode_path_segment)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Rejects sorting a listing by size, which the API can't do
+pub(crate) fn check_list_sort(sort_by: Option<&SortBy>) -> Result<(), Error> {
+    match sort_by {
+        Some(sort_by) if sort_by.column == Column::Size => Err(Error::InvalidArgument {
+            message: "list_files can't sort by size, use sort_file_objects instead".to_string(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Trims whitespace around a path pasted into a URL-building method, rejecting a path with
+/// nothing else in it
+fn trim_url_path(path: &str) -> Result<&str, Error> {
+    match path.trim() {
+        "" => Err(Error::InvalidArgument {
+            message: "path must not be empty".to_string(),
+        }),
+        path => Ok(path),
+    }
+}
+
+/// Percent-encodes everything in a path segment except unreserved characters
+fn encode_path_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Extracts the percent-decoded `token` query parameter from a signed url
+///
+/// The url may be relative, as returned by the API. When `token` appears more than once, the
+/// first is used.
+pub fn extract_token(url: &str) -> Result<String, Error> {
+    // Signed urls are often returned without a host, so resolve them against a placeholder
+    let url = Url::parse("http://localhost")
+        .and_then(|base| base.join(url))
+        .map_err(|_| Error::UrlParseError {
+            message: "Failed to parse Url".to_string(),
+        })?;
+
+    url.query_pairs()
+        .find(|(key, _)| key == "token")
+        .map(|(_, token)| token.into_owned())
+        .filter(|token| !token.is_empty())
         .ok_or_else(|| Error::InvalidToken {
             message: "No token found in URL".to_string(),
         })
@@ -1,12 +1,23 @@
-use std::env;
+use std::{env, io};
 
-use reqwest::StatusCode;
+use reqwest::{Method, StatusCode};
 use thiserror::Error;
 
+use crate::models::StorageErrorBody;
+
 #[derive(Debug, Error)]
 pub enum Error {
-    #[error("Operation failed with status: {status}: {message}")]
-    StorageError { status: StatusCode, message: String },
+    /// The storage API rejected the request
+    ///
+    /// `path` is the request's URL path, without the query string, so signed URL tokens aren't
+    /// included.
+    #[error("Operation {method} {path} failed with status: {status}: {message}")]
+    StorageError {
+        status: StatusCode,
+        message: String,
+        method: Method,
+        path: String,
+    },
     #[error("Environment Variable Unreadable")]
     InvalidEnvironmentVariable(#[from] env::VarError),
     #[error("Failed to Serialize or Deserialize")]
@@ -15,8 +26,99 @@ pub enum Error {
     InvalidHeaderValue(#[from] reqwest::header::InvalidHeaderValue),
     #[error("Failed to send request")]
     RequestError(#[from] reqwest::Error),
+    /// A middleware of a client created with `StorageClient::new_with_middleware` failed
+    ///
+    /// Only returned with the `middleware` feature, but always defined so enabling the feature
+    /// doesn't break exhaustive matches.
+    #[error("Middleware failed: {message}")]
+    MiddlewareError { message: String },
     #[error("ParseError: {message}")]
     UrlParseError { message: String },
     #[error("InvalidToken: {message}")]
     InvalidToken { message: String },
+    /// The api key or JWT is missing, invalid or expired
+    #[error("Unauthorized: {message}")]
+    Unauthorized { message: String },
+    /// The request was authenticated, but denied (e.g. by a Row Level Security policy)
+    #[error("Forbidden: {message}")]
+    Forbidden { message: String },
+    /// The requested object doesn't exist
+    #[error("NotFound: {message}")]
+    NotFound { message: String },
+    /// A bucket with the same id, or an object at the same path (`bucket_id/path`), already
+    /// exists
+    #[error("AlreadyExists: {name} already exists")]
+    AlreadyExists { name: String },
+    /// The request was aborted through its `CancellationToken`
+    #[error("Request was cancelled")]
+    Cancelled,
+    /// The feature isn't available on the project's plan, e.g. image transformations outside of
+    /// the Pro plan
+    #[error("{feature} are not available on this plan: {message}")]
+    FeatureNotAvailable { feature: String, message: String },
+    /// The upload's content type isn't one of the bucket's allowed mime types
+    #[error("Mime type {mime} is not allowed, expected one of: {allowed:?}")]
+    MimeTypeNotAllowed { mime: String, allowed: Vec<String> },
+    /// A timestamp returned by the API isn't valid RFC 3339
+    #[error("InvalidTimestamp: {message}")]
+    InvalidTimestamp { message: String },
+    /// A check made after an operation found it didn't have the expected effect, e.g. a verified
+    /// move that left the source behind
+    #[error("VerificationFailed: {message}")]
+    VerificationFailed { message: String },
+    /// An argument was rejected before any request was sent
+    #[error("InvalidArgument: {message}")]
+    InvalidArgument { message: String },
+    /// The API rejected the request body, with a `(field, message)` pair for each invalid field,
+    /// e.g. `("file_size_limit", "must be integer")`
+    #[error("Validation failed: {field_errors:?}")]
+    Validation { field_errors: Vec<(String, String)> },
+    /// A streamed upload's stream didn't yield the `content_length` it was declared with
+    ///
+    /// When the stream overruns the declared length the upload is aborted at once, so `actual`
+    /// counts the bytes read up to that point rather than the whole stream.
+    #[error("ContentLengthMismatch: declared {declared} bytes, but the stream yielded {actual}")]
+    ContentLengthMismatch { declared: u64, actual: u64 },
+    /// The server failed the request with a body that isn't JSON, e.g. an HTML error page from a
+    /// proxy, with the response's status and content type and the body truncated to
+    /// `BODY_SNIPPET_LEN` characters
+    #[error("Unexpected non-JSON response with status: {status}: {body_snippet}")]
+    UnexpectedResponse {
+        status: StatusCode,
+        content_type: Option<String>,
+        body_snippet: String,
+    },
+}
+
+/// Converts to an `io::Error` for `io::Result` based helpers, keeping the original `Error` as its
+/// inner error
+///
+/// Missing objects map to `io::ErrorKind::NotFound`, including the API's `400`s with a `404`
+/// status code in the body, duplicates to `AlreadyExists`, rejected
+/// credentials to `PermissionDenied` and bad arguments to `InvalidInput`. Network and parse
+/// errors, and anything else, map to `Other`.
+impl From<Error> for io::Error {
+    fn from(error: Error) -> Self {
+        let kind = match &error {
+            Error::NotFound { .. } => io::ErrorKind::NotFound,
+            Error::StorageError {
+                status, message, ..
+            } if *status == StatusCode::NOT_FOUND
+                || serde_json::from_str::<StorageErrorBody>(message)
+                    .ok()
+                    .and_then(|error_body| error_body.status_code())
+                    == Some(StatusCode::NOT_FOUND) =>
+            {
+                io::ErrorKind::NotFound
+            }
+            Error::AlreadyExists { .. } => io::ErrorKind::AlreadyExists,
+            Error::Unauthorized { .. } | Error::Forbidden { .. } => io::ErrorKind::PermissionDenied,
+            Error::InvalidArgument { .. } | Error::Validation { .. } => io::ErrorKind::InvalidInput,
+            Error::RequestError(err) if err.is_timeout() => io::ErrorKind::TimedOut,
+            Error::Cancelled => io::ErrorKind::Interrupted,
+            _ => io::ErrorKind::Other,
+        };
+
+        io::Error::new(kind, error)
+    }
 }
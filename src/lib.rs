@@ -20,6 +20,14 @@ cargo add supabase-storage-rs
 
 # Usage
 
+### Imports
+
+The most commonly used types are re-exported from the prelude
+
+```rust
+use supabase_storage_rs::prelude::*;
+```
+
 ### Create a Storage Client
 ```rust
 // You can manually pass in the values
@@ -30,6 +38,15 @@ let auth_client = StorageClient::new(project_url, api_key, jwt_secret).unwrap();
 let auth_client = StorageClient::new_from_env().unwrap();
 ```
 
+A project url without a scheme, like `<project ref>.supabase.co`, is assumed to be `https://`. Spell out `http://` for local instances.
+
+Requests are sent with `DEFAULT_USER_AGENT` as their `User-Agent`. Use `with_user_agent`, or `user_agent` on the builder, to send your own, e.g. for proxies that filter on it.
+
+Enable the `middleware` feature to send requests through a [`reqwest-middleware`](https://crates.io/crates/reqwest-middleware) client, e.g. with retry or tracing middleware, using `StorageClient::new_with_middleware`.
+
+`StorageClient` implements the `StorageBackend` trait, which covers the core object operations. Write your code against `&dyn StorageBackend` to swap in a fake in your own tests.
+Enable the `testing` feature for `testing::MemoryStorage`, an in-memory implementation that needs no network or credentials.
+
 ### Create a Bucket
 
 Create a new storage bucket. Returns the bucket name (not ID) on success.
@@ -40,13 +57,17 @@ let name = client
        "a-cool-name-for-a-bucket",
        None,    // Optional bucket ID
        false,   // Public bucket
-       None,    // Allowed MIME types
+       Some(["image/png", "image/jpeg"]), // Allowed MIME types, as strings or `MimeType`s
        None     // File size limit
    )
    .await
    .unwrap();
 ```
 
+Pass `None::<Vec<MimeType>>` to allow all MIME types.
+
+If the API rejects an option, e.g. a size limit above the project's limit, `create_bucket` and `update_bucket` fail with `Error::Validation`, listing each invalid field with its message.
+
 ### Delete a Bucket
 
 ```rust
@@ -60,7 +81,7 @@ client
    .update_bucket(
        "bucket_id",
        true,  // Make bucket public
-       None,  // Keep existing MIME types
+       None::<Vec<MimeType>>,  // Keep existing MIME types
        None   // Keep existing size limit
    )
    .await
@@ -76,15 +97,19 @@ let bucket = client.get_bucket("a-cool-name-for-a-bucket-with-options").await.un
 pub struct Bucket {
     pub id: String,              // Bucket ID
     pub name: String,            // Bucket name
-    pub owner: String,           // Owner's ID
+    pub owner: Option<String>,   // Owner's ID, `None` if unowned
     pub public: bool,            // Public/private status
     pub file_size_limit: Option<i64>,         // Max file size in bytes
     pub allowed_mime_types: Option<Vec<String>>, // Allowed file types
     pub created_at: String,      // Creation timestamp
     pub updated_at: String,      // Last update timestamp
+    pub created_by: Option<String>, // Creator's ID, on newer API versions
+    pub updated_by: Option<String>, // Last updater's ID, on newer API versions
 }
 ```
 
+Enable the `chrono` or `time` feature to parse the timestamps with accessors like `created_at_datetime()` (`chrono::DateTime<Utc>`) or `created_at_offset_datetime()` (`time::OffsetDateTime`).
+
 ### List Files in a Bucket
 
 ```rust
@@ -96,6 +121,7 @@ let options = FileSearchOptions {
        order: Order::Asc,    // In ascending order
    }),
    search: None,       // No search string
+   include_metadata: None, // Keep object metadata
 };
 
 client
@@ -108,6 +134,12 @@ client
    .unwrap();
 ```
 
+Use `list_files_with_signed_urls` to list the files in a folder together with a signed URL for each, signed in batches.
+
+Use `list_tree` to list a folder and its subfolders as a nested `FileTree`, e.g. for a file explorer, descending up to a given depth.
+
+To download the files of a listing, call `file.download(&client, "bucket_id", "folder/")` on each `FileObject`, which joins its name onto the prefix it was listed under.
+
 ### List Buckets
 
 ```rust
@@ -117,9 +149,11 @@ let buckets = client.list_buckets().await.unwrap();
 ### Empty a Bucket
 
 ```rust
-let empty = client.empty_bucket("bucket_id").await.unwrap();
+let outcome = client.empty_bucket("bucket_id").await.unwrap();
 ```
 
+Calls the server confirms with a free-form message, like `empty_bucket` and `move_file`, return `Outcome::Success` for any 2xx response. The raw message is available with `outcome.message()`, but varies across API versions.
+
 ### Upload a File
 
 ```rust
@@ -134,6 +168,8 @@ let object = client
    .unwrap();
 ```
 
+Enable the `infer` feature and set `FileOptions::detect_content_type` to detect the content type of files uploaded without one from their magic number. Without the feature the option is accepted but ignored.
+
 ### Update a File
 
 ```rust
@@ -161,6 +197,12 @@ let file = client
    .unwrap();
 ```
 
+Objects stored with a `Content-Encoding` are returned as stored. Enable the `decompress` feature to decode gzip and brotli bodies transparently, or use `download_file_response` to inspect the encoding yourself.
+
+To reuse a cached copy, pass the `etag` from a previous `download_file_response` to `download_file_if_modified`, which returns `ConditionalDownload::NotModified` instead of the body when the object hasn't changed.
+
+`download_file_response` also returns the object's `last_modified` header, e.g. for comparing with a local mtime in a sync tool. Enable the `chrono` or `time` feature to parse it with `last_modified_datetime()` or `last_modified_offset_datetime()`.
+
 ### Copy a File
 
 ```rust
@@ -189,6 +231,8 @@ let key = client
    .unwrap();
 ```
 
+Use `copy_file_with_options` or `move_file_with_options` to set a new cache control or content type on the destination, e.g. when promoting a draft to a cached public asset.
+
 ### Delete a File
 
 ```rust
@@ -214,26 +258,33 @@ let signed_url = client
    .unwrap();
 ```
 
+Expiry times of zero or longer than a year are rejected with `Error::InvalidArgument`. Use `with_max_signed_url_expiry` to change the limit.
+
 ### Create Multiple Signed URLs
 
 ```rust
-let urls = client
+let entries = client
    .create_multiple_signed_urls(
        "bucket_id",
        vec!["1.txt", "2.txt", "3.txt"], // File paths
-       100_000                          // Expiry time in seconds
+       100_000,                         // Expiry time in seconds
+       None                             // Max concurrency, defaults to the client's
    )
    .await
    .unwrap();
 ```
 
+Returns one `SignedUrlEntry` per path, in the same order. Paths that couldn't be signed, e.g. because the object doesn't exist, have no `signed_url` and the reason in `error`. Lists longer than `SIGN_BATCH_SIZE` are split into several requests transparently, sent up to `max_concurrency` at a time.
+
 ### Create a Signed Upload URL
 
 Returns SignedUploadUrlResponse containing:
 
-`url`: Path without hostname
+`url`: Fully-qualified URL, including the hostname
 `token`: Authorization token
 
+Use `signed.path()` for the URL's path relative to `/storage/v1`, always starting with `/`.
+
 ```rust
 let signed = client
    .create_signed_upload_url(
@@ -291,6 +342,19 @@ let url = client
    .unwrap();
 ```
 
+### reqwest in the Public API
+
+Responses are always read into crate-owned types like `DownloadResponse`, so no method returns a raw `reqwest::Response`. A few places deliberately expose `reqwest` types, which tie them to the `reqwest` version this crate depends on:
+
+- `StorageClient::client` and `StorageClientBuilder::http_client` (`reqwest::Client`)
+- `StorageClient::with_request_middleware` (`reqwest::RequestBuilder`)
+- `StorageClient::insert_header` (`reqwest::header::IntoHeaderName`)
+- `StorageClient::public_url_parsed` (`reqwest::Url`)
+- `Error`, whose variants carry a `reqwest::StatusCode`, `reqwest::Method` or `reqwest::Error`
+- `StorageClient::new_with_middleware`, with the `middleware` feature (`reqwest_middleware::ClientWithMiddleware`)
+
+Use the `supabase_storage_rs::reqwest` re-export to name these types without depending on a matching `reqwest` version yourself.
+
 ## Features
 - [x] Create Bucket
 - [x] Delete Bucket
@@ -318,6 +382,17 @@ let url = client
 Contributors are always welcome. I only ask that you add or update tests to cover your changes. Until this crate reaches 1.0.0 we're in the "move fast and break things" phase. Don't concern yourself with elegance.
 */
 
+/// The `reqwest` this crate is built against, for naming the reqwest types in its public API
+/// without depending on a matching version yourself
+pub use reqwest;
+/// The `reqwest-middleware` this crate is built against, see `StorageClient::new_with_middleware`
+#[cfg(feature = "middleware")]
+pub use reqwest_middleware;
+
+pub mod backend;
 pub mod client;
 pub mod errors;
 pub mod models;
+pub mod prelude;
+#[cfg(feature = "testing")]
+pub mod testing;
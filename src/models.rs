@@ -1,9 +1,24 @@
-use std::{fmt, time::Duration};
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt,
+    hash::{Hash, Hasher},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
-use reqwest::{header::HeaderMap, Client};
-use serde::{Deserialize, Serialize};
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    Client, Proxy, RequestBuilder, StatusCode,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::errors::Error;
 
 /// Supabase Storage Client
+///
+/// Cloning is cheap and clones share a connection pool, so a client can be cloned into each task
+/// rather than wrapped in an `Arc`.
 #[derive(Clone)]
 pub struct StorageClient {
     pub client: Client,
@@ -13,6 +28,93 @@ pub struct StorageClient {
     /// WARN: The `service role` key has the ability to bypass Row Level Security. Never share it publicly.
     pub api_key: String,
     pub(crate) headers: HeaderMap,
+    /// The most requests a batch helper sends at once, see `StorageClient::with_max_concurrency`
+    pub(crate) max_concurrency: usize,
+    /// Extra query parameters appended to every request, see `StorageClient::with_query`
+    pub(crate) query: Vec<(String, String)>,
+    /// How many times an idempotent upload is retried, see `StorageClient::with_max_retries`
+    pub(crate) max_retries: u32,
+    /// The Cache-Control header sent with uploads that don't set their own, see
+    /// `StorageClient::with_default_cache_control`
+    pub(crate) default_cache_control: Option<HeaderValue>,
+    /// Whether the `apikey` header is sent, see `StorageClient::with_send_api_key_header`
+    pub(crate) send_api_key_header: bool,
+    /// The longest signed URL expiry in seconds, see `StorageClient::with_max_signed_url_expiry`
+    pub(crate) max_signed_url_expiry: u64,
+    /// Buckets fetched to validate upload mime types, shared by clones of the client, see
+    /// `StorageClient::invalidate_allowed_mime_types`
+    pub(crate) bucket_cache: Arc<Mutex<HashMap<String, Bucket>>>,
+    /// Applied to every request before it's sent, see `StorageClient::with_request_middleware`
+    pub(crate) request_middleware: Option<RequestMiddleware>,
+    /// Sends requests instead of `client` when set, see `StorageClient::new_with_middleware`
+    #[cfg(feature = "middleware")]
+    pub(crate) middleware_client: Option<reqwest_middleware::ClientWithMiddleware>,
+}
+
+/// Shows the api key as its fingerprint and only the names of headers, which may carry the key
+impl fmt::Debug for StorageClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StorageClient")
+            .field("client", &self.client)
+            .field("project_url", &self.project_url)
+            .field("api_key", &api_key_fingerprint(&self.api_key))
+            .field("headers", &self.headers.keys().collect::<Vec<_>>())
+            .field("max_concurrency", &self.max_concurrency)
+            .field("query", &self.query)
+            .field("max_retries", &self.max_retries)
+            .field("default_cache_control", &self.default_cache_control)
+            .field("send_api_key_header", &self.send_api_key_header)
+            .field("max_signed_url_expiry", &self.max_signed_url_expiry)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A hook that can adjust any request before it's sent
+pub type RequestMiddleware = Arc<dyn Fn(RequestBuilder) -> RequestBuilder + Send + Sync>;
+
+/// Builds a `StorageClient`, created with `StorageClient::builder`
+pub struct StorageClientBuilder {
+    pub(crate) project_url: String,
+    pub(crate) api_key: String,
+    pub(crate) client: Option<Client>,
+    pub(crate) headers: HeaderMap,
+    pub(crate) max_concurrency: usize,
+    pub(crate) query: Vec<(String, String)>,
+    pub(crate) max_retries: u32,
+    pub(crate) proxy: Option<Proxy>,
+    pub(crate) default_cache_control: Option<HeaderValue>,
+    pub(crate) send_api_key_header: bool,
+    pub(crate) max_signed_url_expiry: u64,
+    /// The first invalid header, proxy url or cache control value, reported by `build`
+    pub(crate) error: Option<Error>,
+}
+
+/// Shows the api key as its fingerprint and only the names of headers, which may carry the key
+impl fmt::Debug for StorageClientBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StorageClientBuilder")
+            .field("project_url", &self.project_url)
+            .field("api_key", &api_key_fingerprint(&self.api_key))
+            .field("headers", &self.headers.keys().collect::<Vec<_>>())
+            .field("max_concurrency", &self.max_concurrency)
+            .field("query", &self.query)
+            .field("max_retries", &self.max_retries)
+            .field("default_cache_control", &self.default_cache_control)
+            .field("send_api_key_header", &self.send_api_key_header)
+            .field("max_signed_url_expiry", &self.max_signed_url_expiry)
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A redacted form of an api key that's safe to log, see `StorageClient::api_key_fingerprint`
+pub(crate) fn api_key_fingerprint(api_key: &str) -> String {
+    let chars: Vec<char> = api_key.trim().chars().collect();
+    match chars.len() {
+        0 => String::new(),
+        len if len < API_KEY_FINGERPRINT_MIN_LEN => "****".to_string(),
+        len => format!("****{}", chars[len - 4..].iter().collect::<String>()),
+    }
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -30,6 +132,56 @@ pub(crate) struct CreateBucket<'a> {
     pub file_size_limit: Option<u64>,
 }
 
+/// Whether a bucket's objects can be downloaded without an authorization token
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Visibility {
+    /// Objects can be downloaded by anyone with the public URL
+    Public,
+    /// Objects require an authorization token or signed URL to download
+    #[default]
+    Private,
+}
+
+impl Visibility {
+    /// Whether this is `Visibility::Public`
+    pub fn is_public(&self) -> bool {
+        matches!(self, Visibility::Public)
+    }
+}
+
+impl From<bool> for Visibility {
+    fn from(public: bool) -> Self {
+        match public {
+            true => Visibility::Public,
+            false => Visibility::Private,
+        }
+    }
+}
+
+/// Options used when creating a bucket
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct BucketOptions<'a> {
+    /// The ID of the bucket, defaults to the bucket name
+    pub id: Option<&'a str>,
+    /// The visibility of the bucket. Public buckets don't require an authorization token to download objects, but still require a valid token for all other operations.
+    pub visibility: Visibility,
+    /// The allowed mime types that this bucket can accept during upload. `None` allows all mime types.
+    pub allowed_mime_types: Option<Vec<MimeType<'a>>>,
+    /// The max file size in bytes that can be uploaded to this bucket. No maximum size is set by default.
+    pub file_size_limit: Option<u64>,
+}
+
+/// Options used when updating a bucket
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct UpdateBucketOptions<'a> {
+    /// The visibility of the bucket. Public buckets don't require an authorization token to download objects, but still require a valid token for all other operations.
+    pub visibility: Visibility,
+    /// The allowed mime types that this bucket can accept during upload. `None` allows all mime types.
+    pub allowed_mime_types: Option<Vec<MimeType<'a>>>,
+    /// The max file size in bytes that can be uploaded to this bucket. No maximum size is set by default.
+    pub file_size_limit: Option<u64>,
+}
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub(crate) struct UpdateBucket<'a> {
     /// The ID of the bucket used for making updates or deletion
@@ -43,7 +195,7 @@ pub(crate) struct UpdateBucket<'a> {
     pub file_size_limit: Option<u64>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct FileObject {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -56,6 +208,9 @@ pub struct FileObject {
     pub last_accessed_at: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Metadata>,
+    /// Custom metadata set through `FileOptions::metadata` when the object was uploaded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_metadata: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bucket_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -64,20 +219,189 @@ pub struct FileObject {
     pub buckets: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl FileObject {
+    /// Whether this entry is a folder rather than a file
+    ///
+    /// Folders are returned by `list_files` with only their name populated.
+    pub fn is_folder(&self) -> bool {
+        self.id.is_none()
+    }
+
+    /// The full path of this entry, joining its `name` onto the `prefix` it was listed under
+    ///
+    /// `list_files` returns names relative to the prefix, so this is the key to pass to
+    /// `download_file` and the other object methods.
+    pub fn full_path(&self, prefix: &str) -> String {
+        match prefix.trim_matches('/') {
+            "" => self.name.clone(),
+            prefix => format!("{prefix}/{}", self.name),
+        }
+    }
+
+    /// Download this file from `bucket_id`, given the `prefix` it was listed under
+    ///
+    /// Shorthand for `client.download_file(bucket_id, &file.full_path(prefix), None)`, for
+    /// downloading files straight from a listing. Fails with `Error::InvalidArgument` for folders.
+    ///
+    /// # Example
+    /// ```rust
+    /// for file in client.list_files("bucket_id", Some("folder"), None).await? {
+    ///     let bytes = file.download(&client, "bucket_id", "folder").await?;
+    /// }
+    /// ```
+    pub async fn download(
+        &self,
+        client: &StorageClient,
+        bucket_id: &str,
+        prefix: &str,
+    ) -> Result<Vec<u8>, Error> {
+        if self.is_folder() {
+            return Err(Error::InvalidArgument {
+                message: format!("{} is a folder", self.full_path(prefix)),
+            });
+        }
+
+        client
+            .download_file(bucket_id, &self.full_path(prefix), None)
+            .await
+    }
+
+    /// The size of the file in bytes, or `None` for folders
+    ///
+    /// See `Metadata::byte_len` for how this is chosen.
+    pub fn size(&self) -> Option<i64> {
+        self.metadata.as_ref().map(Metadata::byte_len)
+    }
+
+    /// The mime type of the file, or `None` for folders
+    pub fn mime_type(&self) -> Option<&str> {
+        self.metadata
+            .as_ref()
+            .map(|metadata| metadata.mimetype.as_str())
+    }
+
+    /// Compare two objects by the given column, for sorting client-side
+    ///
+    /// Folders have no `id` or timestamps, so objects missing the column always sort after those
+    /// that have it, regardless of `order`.
+    pub fn compare_by(&self, other: &FileObject, column: Column, order: Order) -> Ordering {
+        match column {
+            Column::Name => compare_present(Some(&self.name), Some(&other.name), order),
+            Column::ID => compare_present(self.id.as_ref(), other.id.as_ref(), order),
+            Column::UpdatedAt => {
+                compare_present(self.updated_at.as_ref(), other.updated_at.as_ref(), order)
+            }
+            Column::CreatedAt => {
+                compare_present(self.created_at.as_ref(), other.created_at.as_ref(), order)
+            }
+            Column::LastAccessedAt => compare_present(
+                self.last_accessed_at.as_ref(),
+                other.last_accessed_at.as_ref(),
+                order,
+            ),
+            Column::Size => compare_present(self.size(), other.size(), order),
+        }
+    }
+}
+
+/// Compares two optional values in `order`, placing missing values last
+fn compare_present<T: Ord>(a: Option<T>, b: Option<T>, order: Order) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => match order {
+            Order::Asc => a.cmp(&b),
+            Order::Desc => b.cmp(&a),
+        },
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Sort a listing client-side, e.g. by a column the server can't sort on
+///
+/// The sort is stable, and objects missing the column (folders) are placed last.
+pub fn sort_file_objects(files: &mut [FileObject], sort_by: &SortBy) {
+    files.sort_by(|a, b| a.compare_by(b, sort_by.column, sort_by.order));
+}
+
+/// The system metadata of a stored object
+///
+/// Only `size` and `mimetype` are always present. The other fields are omitted by some API
+/// versions and for some objects, so they're `None` when missing.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Metadata {
-    #[serde(rename = "eTag")]
-    pub etag: String,
-    pub size: i32,
+    #[serde(rename = "eTag", default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    /// The size recorded when the object was stored, prefer `byte_len` to read it
+    pub size: i64,
     pub mimetype: String,
-    #[serde(rename = "cacheControl")]
-    pub cache_control: String,
-    #[serde(rename = "lastModified")]
-    pub last_modified: String,
-    #[serde(rename = "contentLength")]
-    pub content_length: i32,
-    #[serde(rename = "httpStatusCode")]
-    pub http_status_code: i32,
+    #[serde(
+        rename = "cacheControl",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub cache_control: Option<String>,
+    #[serde(
+        rename = "lastModified",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_modified: Option<String>,
+    #[serde(
+        rename = "contentLength",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub content_length: Option<i64>,
+    #[serde(
+        rename = "httpStatusCode",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub http_status_code: Option<i32>,
+}
+
+impl Metadata {
+    /// The size of the object in bytes
+    ///
+    /// `size` is recorded by the storage API when the object is stored, while `content_length`
+    /// comes from the backing store and reflects the bytes actually served. They usually agree,
+    /// but when they don't `content_length` is authoritative, so it's preferred and `size` is
+    /// only used when the API omits it.
+    pub fn byte_len(&self) -> i64 {
+        self.content_length.unwrap_or(self.size)
+    }
+}
+
+/// A page of `list_files_page` results
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilePage {
+    /// The files and folders on this page
+    pub items: Vec<FileObject>,
+    /// Whether the page was full, so another page probably follows
+    pub has_more: bool,
+}
+
+/// A folder with its files and nested folders, as built by `StorageClient::list_tree`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FileTree {
+    /// The folder's name, empty for the bucket root
+    pub name: String,
+    /// The folder's full path within the bucket, without leading or trailing slashes
+    pub path: String,
+    /// The files directly inside the folder
+    pub files: Vec<FileObject>,
+    /// The folders directly inside the folder, sorted by name
+    pub folders: Vec<FileTree>,
+    /// Whether the folder is deeper than `max_depth`, so its contents weren't listed
+    pub truncated: bool,
+}
+
+impl FileTree {
+    /// The number of files in the tree, including nested folders
+    pub fn file_count(&self) -> usize {
+        self.files.len() + self.folders.iter().map(FileTree::file_count).sum::<usize>()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
@@ -99,6 +423,12 @@ pub struct FileSearchOptions<'a> {
     ///   - uploads/photo1.png
     ///   - photos/vacation/beach.jpg
     pub search: Option<&'a str>,
+    #[serde(skip)]
+    /// Whether to keep each object's `metadata`. Defaults to `true`
+    ///
+    /// The storage API has no lighter names-only listing, so the full listing is still fetched
+    /// and `metadata` is dropped client-side when this is `false`.
+    pub include_metadata: Option<bool>,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -107,7 +437,7 @@ pub struct SortBy {
     pub order: Order,
 }
 
-#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Default, Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum Order {
     #[default]
@@ -115,15 +445,22 @@ pub enum Order {
     Desc,
 }
 
-#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Default, Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum Column {
     #[default]
     Name,
+    // `snake_case` would turn this into `i_d`
+    #[serde(rename = "id")]
     ID,
     UpdatedAt,
     CreatedAt,
     LastAccessedAt,
+    /// The object's size from its metadata
+    ///
+    /// The list endpoint can only sort by the object's own columns, so `list_files` rejects this.
+    /// Sort a listing by size client-side with `sort_file_objects` instead.
+    Size,
 }
 
 // TODO: Forgot to add transform
@@ -160,16 +497,64 @@ pub struct SignedUrlResponse {
     pub signed_url: String,
 }
 
-/// Response containing a time-limited URL (without hostname) for performing a signed upload
+/// One entry of a `create_multiple_signed_urls` response, which has no `signedURL` for paths
+/// that couldn't be signed
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub(crate) struct MultipleSignedUrlResponse {
+    #[serde(default)]
+    pub(crate) path: Option<String>,
+    #[serde(rename = "signedURL", default)]
+    pub(crate) signed_url: Option<String>,
+    #[serde(default)]
+    pub(crate) error: Option<String>,
+}
+
+/// The signed URL created for one of the paths passed to `create_multiple_signed_urls`
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SignedUrlEntry {
+    /// The requested path
+    pub path: String,
+    /// The full signed URL, including the hostname, or `None` when the path couldn't be signed
+    pub signed_url: Option<String>,
+    /// Why the path couldn't be signed, e.g. because the object doesn't exist
+    pub error: Option<String>,
+}
+
+/// A signed download URL along with the token embedded in it
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SignedUrlWithToken {
+    /// The full signed URL, including the hostname
+    pub signed_url: String,
+    /// The token from the URL's `token` query parameter
+    pub token: String,
+}
+
+/// Response containing a time-limited URL for performing a signed upload
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SignedUploadUrlResponse {
-    /// The url, without the hostname
-    /// (Example: /object/upload/sign/upload_tests/tests/signed_upload?token=eyJh...)
+    /// The fully-qualified url, with its path percent-encoded the same way as other urls built
+    /// by the client
+    /// (Example: `https://<project>.supabase.co/storage/v1/object/upload/sign/upload_tests/tests/signed_upload?token=eyJh...`)
     pub url: String,
     /// Authentication token that validates the upload request
     pub token: String,
 }
 
+impl SignedUploadUrlResponse {
+    /// The url's path relative to `STORAGE_V1`, always starting with `/`
+    /// (Example: /object/upload/sign/upload_tests/tests/signed_upload?token=eyJh...)
+    pub fn path(&self) -> &str {
+        self.url
+            .find(STORAGE_V1)
+            .map_or(&self.url, |start| &self.url[start + STORAGE_V1.len()..])
+    }
+
+    /// The fully-qualified signed upload url, rebuilt from `path` against the client's project url
+    pub fn signed_upload_full_url(&self, client: &StorageClient) -> String {
+        format!("{}{}{}", client.project_url, STORAGE_V1, self.path())
+    }
+}
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub(crate) struct CopyFilePayload<'a> {
     #[serde(rename = "bucketId")]
@@ -187,6 +572,21 @@ pub(crate) struct CopyFilePayload<'a> {
     #[serde(rename = "copyMetadata")]
     /// Whether to copy the metadata (like content-type, custom headers) from the source file
     pub(crate) copy_metadata: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Metadata to set on the destination file
+    pub(crate) metadata: Option<ObjectMetadataPayload>,
+}
+
+/// Object metadata that can be set on the destination of a copy
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub(crate) struct ObjectMetadataPayload {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The Content-Type the object is served with
+    pub(crate) mimetype: Option<String>,
+    #[serde(rename = "cacheControl")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The Cache-Control header value the object is served with
+    pub(crate) cache_control: Option<String>,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -219,6 +619,14 @@ pub struct UploadToSignedUrlResponse {
     pub key: String,
 }
 
+/// Payload for deleting multiple files at once
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub(crate) struct DeleteFilesPayload<'a> {
+    #[serde(borrow)]
+    /// The file paths to delete, including the file name (format: folder/subfolder/filename.png)
+    pub(crate) prefixes: Vec<&'a str>,
+}
+
 /// Payload for listing files with optional filtering and sorting
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub(crate) struct ListFilesPayload<'a> {
@@ -257,6 +665,37 @@ pub struct DownloadOptions<'a> {
     pub download: Option<bool>,
 }
 
+/// A downloaded object along with the response headers describing it
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DownloadResponse {
+    /// The body of the object
+    pub data: Vec<u8>,
+    /// The `Content-Type` the object was served with
+    pub content_type: Option<String>,
+    /// The `Content-Encoding` the object was served with, such as `gzip` or `br`
+    ///
+    /// With the `decompress` feature enabled, gzip and brotli bodies are decoded by reqwest
+    /// before they reach `data`, and the header is removed, so this will be `None` for them.
+    pub content_encoding: Option<String>,
+    /// The `ETag` the object was served with, to pass to `download_file_if_modified` later
+    pub etag: Option<String>,
+    /// The raw `Last-Modified` header the object was served with, an HTTP date like
+    /// `Wed, 21 Oct 2015 07:28:00 GMT`
+    ///
+    /// Enable the `chrono` or `time` feature to parse it, e.g. to compare with a local mtime.
+    pub last_modified: Option<String>,
+}
+
+/// The result of a conditional download with `download_file_if_modified`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConditionalDownload {
+    /// The object changed since the given `ETag` was issued, or the `ETag` didn't match
+    Modified(DownloadResponse),
+    /// The object still matches the given `ETag`, so the server sent `304 Not Modified` and no
+    /// body
+    NotModified,
+}
+
 /// Options for image transformation operations
 ///
 /// Provides configuration for resizing, reformatting, and quality adjustments of images
@@ -283,6 +722,9 @@ pub struct TransformOptions<'a> {
     /// Cover resizes the image to maintain it's aspect ratio while filling the entire width and height.
     /// Contain resizes the image to maintain it's aspect ratio while fitting the entire image within the width and height.
     /// Fill resizes the image to fill the entire width and height. If the object's aspect ratio does not match the width and height, the image will be stretched to fit.
+    ///
+    /// The render API has no gravity or focal point parameter, so cover always crops around the
+    /// center of the image. To keep a specific region, crop the image before uploading it.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub resize: Option<&'a str>,
     /// Specify the format of the image requested.
@@ -298,6 +740,18 @@ pub struct TransformOptions<'a> {
     pub quality: Option<u8>,
 }
 
+impl TransformOptions<'_> {
+    /// The `Accept` header matching the requested `format`, e.g. `image/avif` for `avif`
+    ///
+    /// Returns `None` when no format is requested, or for `origin`, which keeps the original format.
+    pub fn accept_header(&self) -> Option<String> {
+        match self.format {
+            None | Some("origin") => None,
+            Some(format) => Some(format!("image/{format}")),
+        }
+    }
+}
+
 /// Configuration options for file uploads to Supabase Storage
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Upload<'a> {
@@ -316,30 +770,307 @@ pub struct FileOptions<'a> {
     /// Sets the Cache-Control: max-age=<seconds> header
     /// Defaults to 3600 seconds
     pub cache_control: Option<Duration>,
+    /// A raw Cache-Control header value, forwarded verbatim (e.g. `"max-age=3600, immutable"`)
+    /// Takes precedence over `cache_control` when both are set
+    pub cache_control_header: Option<&'a str>,
     /// The Content-Type header value
     /// Required if using a fileBody that is neither Blob, File, nor FormData
     /// Defaults to "text/plain;charset=UTF-8"
     pub content_type: Option<&'a str>,
-    /// Enables or disables duplex streaming for reading and writing data in the same stream
+    /// Enables duplex streaming for reading and writing data in the same stream
+    ///
+    /// When set (e.g. to `"half"`), the file body is streamed to the server in chunks using
+    /// chunked transfer encoding instead of being sent with a `Content-Length`.
+    ///
+    /// This only changes the transfer encoding, the file is still held in memory in full. Use
+    /// `upload_file_stream` to upload from a stream without buffering it.
     pub duplex: Option<&'a str>,
     /// When true, the file is overwritten if it exists
     /// When false, an error is thrown if the object already exists
     /// Defaults to false
     pub upsert: bool,
+    /// The Content-Disposition header value, forwarded with the upload
+    ///
+    /// Supabase doesn't persist this per object. To have a download suggest saving the file,
+    /// use `DownloadOptions::download`, which sets the disposition when the object is served.
+    pub content_disposition: Option<&'a str>,
+    /// Custom metadata stored alongside the object, sent as the `x-metadata` header
+    ///
+    /// It's returned as `FileObject::user_metadata` when listing files.
+    pub metadata: Option<serde_json::Value>,
+    /// When true, `content_type` is checked against the bucket's allowed mime types before
+    /// uploading, failing with `Error::MimeTypeNotAllowed` without sending the file
+    ///
+    /// The bucket is fetched on the first validated upload to it and cached on the client, so it
+    /// requires `select` permission on it. Call `StorageClient::invalidate_allowed_mime_types`
+    /// after changing the bucket outside this client.
+    /// Uploads without a `content_type` are left for the server to check.
+    pub validate_mime_type: bool,
+    /// When true and `content_type` is `None`, `upload_file` and `update_file` detect the content
+    /// type from the file's magic number, e.g. `image/png` for PNG bytes
+    ///
+    /// Unrecognised bytes are uploaded without a content type, as if this were false.
+    /// Without the `infer` feature this does nothing.
+    pub detect_content_type: bool,
+    /// When true, `If-None-Match: *` is sent so the server rejects the upload if the object
+    /// already exists, failing with `Error::AlreadyExists`
+    ///
+    /// Unlike checking `exists` first, this can't race with a concurrent upload. It can't be
+    /// combined with `upsert`.
+    pub create_only: bool,
+}
+
+impl FileOptions<'_> {
+    /// Parse the `max-age` directive out of a Cache-Control header value
+    ///
+    /// Returns `None` if there is no valid `max-age`. Use `cache_control_header` instead to keep
+    /// the other directives, like `public` or `immutable`.
+    ///
+    /// # Example
+    /// ```
+    /// let max_age = FileOptions::cache_control_from_str("max-age=3600, public");
+    /// assert_eq!(max_age, Some(Duration::from_secs(3600)));
+    /// ```
+    pub fn cache_control_from_str(value: &str) -> Option<Duration> {
+        value.split(',').find_map(|directive| {
+            let (name, seconds) = directive.trim().split_once('=')?;
+            if !name.trim().eq_ignore_ascii_case("max-age") {
+                return None;
+            }
+            seconds.trim().parse().ok().map(Duration::from_secs)
+        })
+    }
+
+    /// The Cache-Control header value to send, if any
+    pub(crate) fn cache_control_value(&self) -> Option<String> {
+        match (self.cache_control_header, self.cache_control) {
+            (Some(header), _) => Some(header.to_string()),
+            (None, Some(cache_control)) => Some(cache_control.as_secs().to_string()),
+            (None, None) => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Bucket {
     pub id: String,
     pub name: String,
-    pub owner: String,
+    /// The owner's user id, or `None` when the bucket has no owner
+    #[serde(default, deserialize_with = "deserialize_empty_as_none")]
+    pub owner: Option<String>,
     pub public: bool,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The max file size in bytes, returned by some API versions as a string
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_string_or_number"
+    )]
     pub file_size_limit: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub allowed_mime_types: Option<Vec<String>>,
     pub created_at: String,
     pub updated_at: String,
+    /// The id of the user who created the bucket, only returned by newer API versions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+    /// The id of the user who last updated the bucket, only returned by newer API versions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_by: Option<String>,
+}
+
+impl Bucket {
+    /// Whether the bucket accepts uploads with the given content type
+    ///
+    /// Buckets without `allowed_mime_types` accept everything. Wildcard entries like `image/*`
+    /// match any subtype, and parameters such as `;charset=UTF-8` are ignored.
+    pub fn allows_mime_type(&self, mime: &str) -> bool {
+        let Some(allowed) = self.allowed_mime_types.as_ref().filter(|a| !a.is_empty()) else {
+            return true;
+        };
+
+        let mime = mime.split(';').next().unwrap_or_default().trim();
+
+        allowed.iter().any(|allowed| {
+            let allowed = allowed.trim();
+            match allowed.strip_suffix("/*") {
+                Some("*") => true,
+                Some(kind) => mime
+                    .split_once('/')
+                    .is_some_and(|(mime_kind, _)| mime_kind.eq_ignore_ascii_case(kind)),
+                None => mime.eq_ignore_ascii_case(allowed),
+            }
+        })
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Bucket {
+    /// `created_at` parsed as a `chrono::DateTime<Utc>`
+    pub fn created_at_datetime(&self) -> Result<chrono::DateTime<chrono::Utc>, Error> {
+        parse_chrono_timestamp(&self.created_at)
+    }
+
+    /// `updated_at` parsed as a `chrono::DateTime<Utc>`
+    pub fn updated_at_datetime(&self) -> Result<chrono::DateTime<chrono::Utc>, Error> {
+        parse_chrono_timestamp(&self.updated_at)
+    }
+}
+
+#[cfg(feature = "time")]
+impl Bucket {
+    /// `created_at` parsed as a `time::OffsetDateTime`
+    pub fn created_at_offset_datetime(&self) -> Result<time::OffsetDateTime, Error> {
+        parse_time_timestamp(&self.created_at)
+    }
+
+    /// `updated_at` parsed as a `time::OffsetDateTime`
+    pub fn updated_at_offset_datetime(&self) -> Result<time::OffsetDateTime, Error> {
+        parse_time_timestamp(&self.updated_at)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FileObject {
+    /// `created_at` parsed as a `chrono::DateTime<Utc>`, or `None` for folders
+    pub fn created_at_datetime(&self) -> Result<Option<chrono::DateTime<chrono::Utc>>, Error> {
+        self.created_at
+            .as_deref()
+            .map(parse_chrono_timestamp)
+            .transpose()
+    }
+
+    /// `updated_at` parsed as a `chrono::DateTime<Utc>`, or `None` for folders
+    pub fn updated_at_datetime(&self) -> Result<Option<chrono::DateTime<chrono::Utc>>, Error> {
+        self.updated_at
+            .as_deref()
+            .map(parse_chrono_timestamp)
+            .transpose()
+    }
+
+    /// `last_accessed_at` parsed as a `chrono::DateTime<Utc>`, or `None` for folders
+    pub fn last_accessed_at_datetime(
+        &self,
+    ) -> Result<Option<chrono::DateTime<chrono::Utc>>, Error> {
+        self.last_accessed_at
+            .as_deref()
+            .map(parse_chrono_timestamp)
+            .transpose()
+    }
+}
+
+#[cfg(feature = "time")]
+impl FileObject {
+    /// `created_at` parsed as a `time::OffsetDateTime`, or `None` for folders
+    pub fn created_at_offset_datetime(&self) -> Result<Option<time::OffsetDateTime>, Error> {
+        self.created_at
+            .as_deref()
+            .map(parse_time_timestamp)
+            .transpose()
+    }
+
+    /// `updated_at` parsed as a `time::OffsetDateTime`, or `None` for folders
+    pub fn updated_at_offset_datetime(&self) -> Result<Option<time::OffsetDateTime>, Error> {
+        self.updated_at
+            .as_deref()
+            .map(parse_time_timestamp)
+            .transpose()
+    }
+
+    /// `last_accessed_at` parsed as a `time::OffsetDateTime`, or `None` for folders
+    pub fn last_accessed_at_offset_datetime(&self) -> Result<Option<time::OffsetDateTime>, Error> {
+        self.last_accessed_at
+            .as_deref()
+            .map(parse_time_timestamp)
+            .transpose()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl DownloadResponse {
+    /// `last_modified` parsed as a `chrono::DateTime<Utc>`, or `None` if it wasn't sent
+    pub fn last_modified_datetime(&self) -> Result<Option<chrono::DateTime<chrono::Utc>>, Error> {
+        self.last_modified
+            .as_deref()
+            .map(|last_modified| {
+                chrono::DateTime::parse_from_rfc2822(last_modified)
+                    .map(|datetime| datetime.with_timezone(&chrono::Utc))
+                    .map_err(|err| Error::InvalidTimestamp {
+                        message: format!("{last_modified}: {err}"),
+                    })
+            })
+            .transpose()
+    }
+}
+
+#[cfg(feature = "time")]
+impl DownloadResponse {
+    /// `last_modified` parsed as a `time::OffsetDateTime`, or `None` if it wasn't sent
+    pub fn last_modified_offset_datetime(&self) -> Result<Option<time::OffsetDateTime>, Error> {
+        self.last_modified
+            .as_deref()
+            .map(|last_modified| {
+                time::OffsetDateTime::parse(
+                    last_modified,
+                    &time::format_description::well_known::Rfc2822,
+                )
+                .map_err(|err| Error::InvalidTimestamp {
+                    message: format!("{last_modified}: {err}"),
+                })
+            })
+            .transpose()
+    }
+}
+
+/// Parses an RFC 3339 timestamp returned by the API into a `chrono::DateTime<Utc>`
+#[cfg(feature = "chrono")]
+fn parse_chrono_timestamp(timestamp: &str) -> Result<chrono::DateTime<chrono::Utc>, Error> {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .map(|datetime| datetime.with_timezone(&chrono::Utc))
+        .map_err(|err| Error::InvalidTimestamp {
+            message: format!("{timestamp}: {err}"),
+        })
+}
+
+/// Parses an RFC 3339 timestamp returned by the API into a `time::OffsetDateTime`
+#[cfg(feature = "time")]
+fn parse_time_timestamp(timestamp: &str) -> Result<time::OffsetDateTime, Error> {
+    time::OffsetDateTime::parse(timestamp, &time::format_description::well_known::Rfc3339).map_err(
+        |err| Error::InvalidTimestamp {
+            message: format!("{timestamp}: {err}"),
+        },
+    )
+}
+
+/// Deserializes an optional string, treating an empty string the same as a missing value
+fn deserialize_empty_as_none<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Option<String> = Option::deserialize(deserializer)?;
+    Ok(value.filter(|s| !s.is_empty()))
+}
+
+/// Deserializes an optional integer that may be sent either as a number or a numeric string
+fn deserialize_string_or_number<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrNumber {
+        Number(i64),
+        String(String),
+    }
+
+    match Option::<StringOrNumber>::deserialize(deserializer)? {
+        Some(StringOrNumber::Number(number)) => Ok(Some(number)),
+        Some(StringOrNumber::String(string)) => string
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+        None => Ok(None),
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -352,20 +1083,133 @@ pub struct ObjectResponse {
 
 pub type Buckets = Vec<Bucket>;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// The response to creating a bucket, which is `{ "name": ... }` or `{ "Key": ... }` depending
+/// on the API version
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CreateBucketResponse {
-    pub(crate) name: String,
+    #[serde(default, alias = "Key")]
+    pub(crate) name: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct BucketResponse {
-    pub(crate) message: String,
+    /// The confirmation message, e.g. "Successfully deleted"
+    pub message: String,
 }
 
+/// The outcome of a request the API confirms with a free-form message, like `move_file`
+///
+/// Any 2xx response is a success. The confirmation text varies across API versions, so match
+/// on the variant rather than the message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// The request succeeded, with the server's message if it sent one, e.g. "Successfully moved"
+    Success { message: Option<String> },
+}
+
+impl Outcome {
+    /// Whether the request succeeded
+    pub fn is_success(&self) -> bool {
+        matches!(self, Outcome::Success { .. })
+    }
+
+    /// The raw message the server confirmed the request with, if any
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            Outcome::Success { message } => message.as_deref(),
+        }
+    }
+}
+
+/// The JSON body the storage API responds with when a request fails
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub(crate) struct StorageErrorBody {
+    #[serde(rename = "statusCode")]
+    /// The status of the error, which may differ from the response status
+    /// Sent as either a string or a number depending on the API version
+    pub(crate) status_code: Option<serde_json::Value>,
+    /// A machine readable error code, e.g. `FeatureNotEnabled`, sent by newer API versions
+    pub(crate) code: Option<String>,
+    /// A short name for the error, e.g. `Bucket not found`
+    pub(crate) error: Option<String>,
+    /// A description of the error
+    pub(crate) message: Option<String>,
+}
+
+impl StorageErrorBody {
+    /// The `statusCode` from the body, if present and valid
+    pub(crate) fn status_code(&self) -> Option<StatusCode> {
+        let code = match self.status_code.as_ref()? {
+            serde_json::Value::String(code) => code.parse().ok()?,
+            serde_json::Value::Number(code) => u16::try_from(code.as_u64()?).ok()?,
+            _ => return None,
+        };
+
+        StatusCode::from_u16(code).ok()
+    }
+
+    /// Whether the error reports a conflict with an existing resource, which the API sends as a
+    /// `400` with a `409` status code in the body
+    pub(crate) fn is_duplicate(&self) -> bool {
+        self.status_code() == Some(StatusCode::CONFLICT)
+            || [&self.code, &self.error]
+                .into_iter()
+                .flatten()
+                .any(|code| code == "Duplicate")
+    }
+
+    /// Whether the error reports that image transformations aren't available on the project's plan
+    pub(crate) fn is_transformation_unavailable(&self) -> bool {
+        let feature_not_enabled = [&self.code, &self.error]
+            .into_iter()
+            .flatten()
+            .any(|code| code == "FeatureNotEnabled");
+        let message = self.message.as_deref().unwrap_or_default().to_lowercase();
+
+        (feature_not_enabled || message.contains("plan") || message.contains("not enabled"))
+            && message.contains("transform")
+    }
+
+    /// The `(field, message)` pairs of a request body that failed schema validation, or none if
+    /// the error isn't a validation error
+    ///
+    /// The API reports them in `message`, e.g. `body/file_size_limit must be integer`, joining
+    /// several with `, `. Nested fields keep their full path, e.g. `allowed_mime_types/0`.
+    pub(crate) fn field_errors(&self) -> Vec<(String, String)> {
+        let Some(message) = self.message.as_deref() else {
+            return Vec::new();
+        };
+        let Some(message) = message.strip_prefix("body") else {
+            return Vec::new();
+        };
+
+        message
+            .split(", body")
+            .map(|part| match part.strip_prefix('/') {
+                Some(part) => part
+                    .split_once(' ')
+                    .map(|(field, message)| (field.to_string(), message.to_string())),
+                None => {
+                    let message = part.trim();
+                    message
+                        .strip_prefix("must have required property '")
+                        .and_then(|field| field.strip_suffix('\''))
+                        .map(|field| (field.to_string(), message.to_string()))
+                }
+            })
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default()
+    }
+}
+
+/// Mime types compare and hash by their string, so `Custom` and `CustomOwned` holding the same
+/// mime type are equal, as is a variant and a custom mime type spelling out its string
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum MimeType<'a> {
     Custom(&'a str),
+    /// A mime type without a variant, converted from an owned `String`
+    CustomOwned(String),
     AAC,
     AbiWord,
     APNG,
@@ -521,9 +1365,113 @@ impl MimeType<'_> {
             MimeType::ThreeGPP => "video/3gpp",
             MimeType::ThreeGPP2 => "video/3gpp2",
             MimeType::SevenZip => "application/x-7z-compressed",
-            MimeType::Custom(mime) => &mime,
+            MimeType::Custom(mime) => mime,
+            MimeType::CustomOwned(mime) => mime,
         }
     }
+
+    /// The variant for a known mime type string, ignoring case and surrounding whitespace
+    ///
+    /// Where several variants share a string, e.g. `JavaScript` and `JavaScriptModule`, the
+    /// first one declared is returned.
+    pub fn from_known(mime: &str) -> Option<MimeType<'static>> {
+        let mime = mime.trim();
+        KNOWN_MIME_TYPES
+            .iter()
+            .find(|known| known.as_str().eq_ignore_ascii_case(mime))
+            .cloned()
+    }
+}
+
+/// Every `MimeType` variant with a fixed string, in declaration order
+const KNOWN_MIME_TYPES: &[MimeType<'static>] = &[
+    MimeType::AAC,
+    MimeType::AbiWord,
+    MimeType::APNG,
+    MimeType::Archive,
+    MimeType::AVIF,
+    MimeType::AVI,
+    MimeType::AmazonKindle,
+    MimeType::BinaryData,
+    MimeType::BMP,
+    MimeType::BZip,
+    MimeType::BZip2,
+    MimeType::CDAudio,
+    MimeType::CShellScript,
+    MimeType::CSS,
+    MimeType::CSV,
+    MimeType::DOC,
+    MimeType::DOCX,
+    MimeType::EOT,
+    MimeType::EPUB,
+    MimeType::GZip,
+    MimeType::GIF,
+    MimeType::HTML,
+    MimeType::Icon,
+    MimeType::ICalendar,
+    MimeType::JAR,
+    MimeType::JPEG,
+    MimeType::JavaScript,
+    MimeType::JSON,
+    MimeType::JSONLD,
+    MimeType::MIDI,
+    MimeType::JavaScriptModule,
+    MimeType::MP3,
+    MimeType::MP4,
+    MimeType::MPEG,
+    MimeType::AppleInstaller,
+    MimeType::ODP,
+    MimeType::ODS,
+    MimeType::ODT,
+    MimeType::OggAudio,
+    MimeType::OggVideo,
+    MimeType::Ogg,
+    MimeType::OpusAudio,
+    MimeType::OTF,
+    MimeType::PNG,
+    MimeType::PDF,
+    MimeType::PHP,
+    MimeType::PPT,
+    MimeType::PPTX,
+    MimeType::RAR,
+    MimeType::RTF,
+    MimeType::ShellScript,
+    MimeType::SVG,
+    MimeType::TAR,
+    MimeType::TIFF,
+    MimeType::MPEGTransportStream,
+    MimeType::TTF,
+    MimeType::PlainText,
+    MimeType::Visio,
+    MimeType::WAV,
+    MimeType::WEBMAudio,
+    MimeType::WEBMVideo,
+    MimeType::WEBP,
+    MimeType::WOFF,
+    MimeType::WOFF2,
+    MimeType::XHTML,
+    MimeType::XLS,
+    MimeType::XLSX,
+    MimeType::XML,
+    MimeType::XUL,
+    MimeType::ZIP,
+    MimeType::ThreeGPP,
+    MimeType::ThreeGPP2,
+    MimeType::SevenZip,
+];
+
+impl PartialEq for MimeType<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for MimeType<'_> {}
+
+impl Hash for MimeType<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
 }
 
 impl fmt::Display for MimeType<'_> {
@@ -532,6 +1480,21 @@ impl fmt::Display for MimeType<'_> {
     }
 }
 
+/// Maps a known mime type string to its variant, and any other string to `MimeType::Custom`
+impl<'a> From<&'a str> for MimeType<'a> {
+    fn from(mime: &'a str) -> Self {
+        MimeType::from_known(mime).unwrap_or(MimeType::Custom(mime))
+    }
+}
+
+/// Maps a known mime type string to its variant, and any other string to
+/// `MimeType::CustomOwned`
+impl From<String> for MimeType<'_> {
+    fn from(mime: String) -> Self {
+        MimeType::from_known(&mime).unwrap_or(MimeType::CustomOwned(mime))
+    }
+}
+
 impl From<MimeType<'_>> for String {
     fn from(mime: MimeType) -> Self {
         mime.to_string()
@@ -540,3 +1503,30 @@ impl From<MimeType<'_>> for String {
 
 pub const HEADER_API_KEY: &str = "apikey";
 pub const STORAGE_V1: &str = "/storage/v1";
+/// The message returned by mutating calls when the server responds with an empty 2xx body
+pub const DEFAULT_SUCCESS_MESSAGE: &str = "Success";
+/// The name of the zero-byte object used to keep an otherwise empty folder
+pub const EMPTY_FOLDER_PLACEHOLDER: &str = ".emptyFolderPlaceholder";
+/// The size of each chunk when streaming an upload body
+pub const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+/// The number of characters of an unexpected response body kept in `Error::UnexpectedResponse`
+pub const BODY_SNIPPET_LEN: usize = 200;
+/// The number of objects requested per page when listing a whole folder
+pub const LIST_PAGE_SIZE: u32 = 100;
+/// The shortest api key whose last 4 characters are shown by `StorageClient::api_key_fingerprint`
+pub const API_KEY_FINGERPRINT_MIN_LEN: usize = 12;
+
+/// The `User-Agent` sent by clients this crate creates, unless overridden with
+/// `StorageClient::with_user_agent`
+pub const DEFAULT_USER_AGENT: &str = concat!("supabase-storage-rs/", env!("CARGO_PKG_VERSION"));
+
+/// The longest signed URL expiry accepted by default, one year in seconds
+pub const DEFAULT_MAX_SIGNED_URL_EXPIRY: u64 = 365 * 24 * 60 * 60;
+/// The maximum number of paths the API accepts in a single `delete_files` call
+pub const DELETE_BATCH_SIZE: usize = 1000;
+/// The maximum number of paths the API accepts in a single `create_multiple_signed_urls` call
+pub const SIGN_BATCH_SIZE: usize = 1000;
+/// The default number of requests a batch helper sends at once
+pub const DEFAULT_MAX_CONCURRENCY: usize = 8;
+/// The deepest level of nested folders descended into when walking a bucket or folder
+pub const MAX_FOLDER_DEPTH: usize = 64;
@@ -0,0 +1,12 @@
+//! Commonly used types, re-exported for a single glob import
+//!
+//! ```rust
+//! use supabase_storage_rs::prelude::*;
+//! ```
+
+pub use crate::backend::StorageBackend;
+pub use crate::errors::Error;
+pub use crate::models::{
+    Column, DownloadOptions, FileOptions, FileSearchOptions, MimeType, Order, Outcome, SortBy,
+    StorageClient, TransformOptions,
+};
@@ -0,0 +1,369 @@
+//! An in-memory `StorageBackend`, for testing code that uses storage without a network or
+//! credentials
+//!
+//! Requires the `testing` feature.
+//!
+//! ```rust
+//! use supabase_storage_rs::{prelude::*, testing::MemoryStorage};
+//!
+//! let storage = MemoryStorage::new().with_bucket("avatars");
+//! storage.upload_file("avatars", b"png".to_vec(), "user.png", None).await.unwrap();
+//!
+//! assert_eq!(storage.download_file("avatars", "user.png", None).await.unwrap(), b"png");
+//! ```
+
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    sync::Mutex,
+};
+
+use futures_util::future::{ready, BoxFuture};
+
+use crate::{
+    backend::StorageBackend,
+    client::check_list_sort,
+    errors::Error,
+    models::{
+        sort_file_objects, BucketResponse, DownloadOptions, FileObject, FileOptions,
+        FileSearchOptions, Metadata, ObjectResponse, Outcome, DEFAULT_SUCCESS_MESSAGE,
+        LIST_PAGE_SIZE,
+    },
+};
+
+/// A `StorageBackend` that keeps its buckets and objects in memory
+///
+/// It mirrors the API's behaviour where tests are likely to depend on it:
+/// - Objects can only be stored in buckets created with `with_bucket` or `create_bucket`
+/// - Uploading over an existing object fails with `Error::AlreadyExists` unless
+///   `FileOptions::upsert` is set, and copying or moving over one always fails
+/// - Reading, updating, deleting, copying or moving a missing object fails with
+///   `Error::NotFound`
+/// - `list_files` returns the folders directly inside a prefix and then the files, each sorted
+///   by name, unless `FileSearchOptions::sort_by` is set. Sorting by `Column::Size` fails with
+///   `Error::InvalidArgument`, as it does for `StorageClient::list_files`
+///
+/// Options that only affect how objects are served, like transforms or cache control, are
+/// ignored.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    buckets: Mutex<HashSet<String>>,
+    objects: Mutex<HashMap<(String, String), Vec<u8>>>,
+}
+
+impl MemoryStorage {
+    /// Create an empty `MemoryStorage`, with no buckets
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an empty bucket, for building the storage up front
+    pub fn with_bucket(self, bucket_id: &str) -> Self {
+        self.create_bucket(bucket_id);
+        self
+    }
+
+    /// Add an empty bucket, returning `false` if it already existed
+    pub fn create_bucket(&self, bucket_id: &str) -> bool {
+        self.buckets.lock().unwrap().insert(bucket_id.to_string())
+    }
+
+    /// Remove a bucket and every object in it, returning `false` if it didn't exist
+    pub fn delete_bucket(&self, bucket_id: &str) -> bool {
+        self.objects
+            .lock()
+            .unwrap()
+            .retain(|(bucket, _), _| bucket != bucket_id);
+        self.buckets.lock().unwrap().remove(bucket_id)
+    }
+
+    /// The contents of an object, or `None` if it doesn't exist
+    pub fn get(&self, bucket_id: &str, path: &str) -> Option<Vec<u8>> {
+        self.objects
+            .lock()
+            .unwrap()
+            .get(&key(bucket_id, path))
+            .cloned()
+    }
+
+    fn check_bucket(&self, bucket_id: &str) -> Result<(), Error> {
+        match self.buckets.lock().unwrap().contains(bucket_id) {
+            true => Ok(()),
+            false => Err(Error::NotFound {
+                message: format!("Bucket not found: {bucket_id}"),
+            }),
+        }
+    }
+
+    fn read(&self, bucket_id: &str, path: &str) -> Result<Vec<u8>, Error> {
+        self.check_bucket(bucket_id)?;
+        self.get(bucket_id, path)
+            .ok_or_else(|| object_not_found(bucket_id, path))
+    }
+
+    fn write(
+        &self,
+        bucket_id: &str,
+        path: &str,
+        data: Vec<u8>,
+        update: bool,
+        upsert: bool,
+    ) -> Result<ObjectResponse, Error> {
+        self.check_bucket(bucket_id)?;
+
+        let mut objects = self.objects.lock().unwrap();
+        let exists = objects.contains_key(&key(bucket_id, path));
+        if update && !exists {
+            return Err(object_not_found(bucket_id, path));
+        }
+        if !update && !upsert && exists {
+            return Err(Error::AlreadyExists {
+                name: format!("{bucket_id}/{path}"),
+            });
+        }
+        objects.insert(key(bucket_id, path), data);
+
+        Ok(ObjectResponse {
+            id: format!("{bucket_id}/{path}"),
+            key: format!("{bucket_id}/{path}"),
+        })
+    }
+
+    fn remove(&self, bucket_id: &str, path: &str) -> Option<Vec<u8>> {
+        self.objects.lock().unwrap().remove(&key(bucket_id, path))
+    }
+
+    fn copy(
+        &self,
+        from_bucket: &str,
+        to_bucket: Option<&str>,
+        from_path: &str,
+        to_path: Option<&str>,
+    ) -> Result<(String, String), Error> {
+        let data = self.read(from_bucket, from_path)?;
+        let to_bucket = to_bucket.unwrap_or(from_bucket);
+        let to_path = to_path.unwrap_or(from_path);
+        self.write(to_bucket, to_path, data, false, false)?;
+
+        Ok((to_bucket.to_string(), to_path.to_string()))
+    }
+
+    fn list(
+        &self,
+        bucket_id: &str,
+        path: Option<&str>,
+        options: Option<FileSearchOptions<'_>>,
+    ) -> Result<Vec<FileObject>, Error> {
+        self.check_bucket(bucket_id)?;
+
+        let options = options.unwrap_or_default();
+        check_list_sort(options.sort_by.as_ref())?;
+        let prefix = match path.unwrap_or("").trim_matches('/') {
+            "" => String::new(),
+            path => format!("{path}/"),
+        };
+
+        let objects = self.objects.lock().unwrap();
+        let mut folders = BTreeSet::new();
+        let mut files = BTreeSet::new();
+        for (bucket, object_path) in objects.keys() {
+            let Some(name) = object_path
+                .strip_prefix(&prefix)
+                .filter(|_| bucket == bucket_id)
+            else {
+                continue;
+            };
+            match name.split_once('/') {
+                Some((folder, _)) => folders.insert(folder),
+                None => files.insert(name),
+            };
+        }
+
+        let search = options.search.unwrap_or("");
+        let mut entries: Vec<FileObject> = folders
+            .into_iter()
+            .map(|name| (name, None))
+            .chain(files.into_iter().map(|name| {
+                let size = objects[&key(bucket_id, &format!("{prefix}{name}"))].len();
+                (name, Some(size))
+            }))
+            .filter(|(name, _)| name.contains(search))
+            .map(|(name, size)| file_object(bucket_id, name, size))
+            .collect();
+
+        // Sorted before paging, as the API does
+        if let Some(sort_by) = &options.sort_by {
+            sort_file_objects(&mut entries, sort_by);
+        }
+
+        Ok(entries
+            .into_iter()
+            .skip(options.offset.unwrap_or(0) as usize)
+            .take(options.limit.unwrap_or(LIST_PAGE_SIZE) as usize)
+            .collect())
+    }
+}
+
+impl StorageBackend for MemoryStorage {
+    fn upload_file<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        data: Vec<u8>,
+        path: &'a str,
+        options: Option<FileOptions<'a>>,
+    ) -> BoxFuture<'a, Result<ObjectResponse, Error>> {
+        let upsert = options.is_some_and(|options| options.upsert);
+        Box::pin(ready(self.write(bucket_id, path, data, false, upsert)))
+    }
+
+    fn update_file<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        data: Vec<u8>,
+        path: &'a str,
+        _options: Option<FileOptions<'a>>,
+    ) -> BoxFuture<'a, Result<ObjectResponse, Error>> {
+        Box::pin(ready(self.write(bucket_id, path, data, true, false)))
+    }
+
+    fn download_file<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: &'a str,
+        _options: Option<DownloadOptions<'a>>,
+    ) -> BoxFuture<'a, Result<Vec<u8>, Error>> {
+        Box::pin(ready(self.read(bucket_id, path)))
+    }
+
+    fn list_files<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: Option<&'a str>,
+        options: Option<FileSearchOptions<'a>>,
+    ) -> BoxFuture<'a, Result<Vec<FileObject>, Error>> {
+        Box::pin(ready(self.list(bucket_id, path, options)))
+    }
+
+    fn exists<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: &'a str,
+    ) -> BoxFuture<'a, Result<bool, Error>> {
+        let exists = self
+            .check_bucket(bucket_id)
+            .map(|_| self.get(bucket_id, path).is_some());
+        Box::pin(ready(exists))
+    }
+
+    fn delete_file<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: &'a str,
+    ) -> BoxFuture<'a, Result<BucketResponse, Error>> {
+        let deleted = self.check_bucket(bucket_id).and_then(|_| {
+            self.remove(bucket_id, path)
+                .map(|_| BucketResponse {
+                    message: DEFAULT_SUCCESS_MESSAGE.to_string(),
+                })
+                .ok_or_else(|| object_not_found(bucket_id, path))
+        });
+        Box::pin(ready(deleted))
+    }
+
+    fn delete_files<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        paths: Vec<&'a str>,
+    ) -> BoxFuture<'a, Result<Vec<FileObject>, Error>> {
+        // Like the API, missing paths are skipped and only the deleted objects are returned
+        let deleted = self.check_bucket(bucket_id).map(|_| {
+            paths
+                .into_iter()
+                .filter_map(|path| {
+                    self.remove(bucket_id, path)
+                        .map(|data| file_object(bucket_id, path, Some(data.len())))
+                })
+                .collect()
+        });
+        Box::pin(ready(deleted))
+    }
+
+    fn copy_file<'a>(
+        &'a self,
+        from_bucket: &'a str,
+        to_bucket: Option<&'a str>,
+        from_path: &'a str,
+        to_path: Option<&'a str>,
+        _copy_metadata: bool,
+    ) -> BoxFuture<'a, Result<String, Error>> {
+        let copied = self
+            .copy(from_bucket, to_bucket, from_path, to_path)
+            .map(|(bucket, path)| format!("{bucket}/{path}"));
+        Box::pin(ready(copied))
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        from_bucket: &'a str,
+        to_bucket: Option<&'a str>,
+        from_path: &'a str,
+        to_path: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Outcome, Error>> {
+        // Moving onto the source itself fails in `copy`, so the source is always removed
+        let moved = self
+            .copy(from_bucket, to_bucket, from_path, to_path)
+            .map(|_| {
+                self.remove(from_bucket, from_path);
+                Outcome::Success {
+                    message: Some("Successfully moved".to_string()),
+                }
+            });
+        Box::pin(ready(moved))
+    }
+
+    fn create_signed_url<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: &'a str,
+        expires_in: u64,
+        _options: Option<DownloadOptions<'a>>,
+    ) -> BoxFuture<'a, Result<String, Error>> {
+        let url = self
+            .read(bucket_id, path)
+            .map(|_| format!("memory://{bucket_id}/{path}?expiresIn={expires_in}"));
+        Box::pin(ready(url))
+    }
+}
+
+fn key(bucket_id: &str, path: &str) -> (String, String) {
+    (bucket_id.to_string(), path.to_string())
+}
+
+fn object_not_found(bucket_id: &str, path: &str) -> Error {
+    Error::NotFound {
+        message: format!("Object not found: {bucket_id}/{path}"),
+    }
+}
+
+/// A listing entry, which is a folder when `size` is `None`
+fn file_object(bucket_id: &str, name: &str, size: Option<usize>) -> FileObject {
+    FileObject {
+        name: name.to_string(),
+        id: size.map(|_| format!("{bucket_id}/{name}")),
+        updated_at: None,
+        created_at: None,
+        last_accessed_at: None,
+        metadata: size.map(|size| Metadata {
+            etag: None,
+            size: size as i64,
+            mimetype: "application/octet-stream".to_string(),
+            cache_control: None,
+            last_modified: None,
+            content_length: Some(size as i64),
+            http_status_code: None,
+        }),
+        user_metadata: None,
+        bucket_id: size.map(|_| bucket_id.to_string()),
+        owner: None,
+        buckets: None,
+    }
+}
@@ -0,0 +1,242 @@
+use std::{collections::BTreeMap, sync::Mutex};
+
+use futures_util::future::BoxFuture;
+use supabase_storage_rs::models::{BucketResponse, FileObject, ObjectResponse, Outcome};
+use supabase_storage_rs::prelude::*;
+
+/// A fake backend keeping objects in memory, keyed by `(bucket_id, path)`
+#[derive(Default)]
+struct FakeStorage {
+    objects: Mutex<BTreeMap<(String, String), Vec<u8>>>,
+}
+
+impl FakeStorage {
+    fn not_found(bucket_id: &str, path: &str) -> Error {
+        Error::NotFound {
+            message: format!("{bucket_id}/{path}"),
+        }
+    }
+
+    fn put(&self, bucket_id: &str, path: &str, data: Vec<u8>) -> ObjectResponse {
+        self.objects
+            .lock()
+            .unwrap()
+            .insert((bucket_id.to_string(), path.to_string()), data);
+        ObjectResponse {
+            id: format!("{bucket_id}/{path}"),
+            key: format!("{bucket_id}/{path}"),
+        }
+    }
+}
+
+impl StorageBackend for FakeStorage {
+    fn upload_file<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        data: Vec<u8>,
+        path: &'a str,
+        _: Option<FileOptions<'a>>,
+    ) -> BoxFuture<'a, Result<ObjectResponse, Error>> {
+        Box::pin(async move { Ok(self.put(bucket_id, path, data)) })
+    }
+
+    fn update_file<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        data: Vec<u8>,
+        path: &'a str,
+        _: Option<FileOptions<'a>>,
+    ) -> BoxFuture<'a, Result<ObjectResponse, Error>> {
+        Box::pin(async move { Ok(self.put(bucket_id, path, data)) })
+    }
+
+    fn download_file<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: &'a str,
+        _: Option<DownloadOptions<'a>>,
+    ) -> BoxFuture<'a, Result<Vec<u8>, Error>> {
+        Box::pin(async move {
+            self.objects
+                .lock()
+                .unwrap()
+                .get(&(bucket_id.to_string(), path.to_string()))
+                .cloned()
+                .ok_or_else(|| Self::not_found(bucket_id, path))
+        })
+    }
+
+    fn list_files<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: Option<&'a str>,
+        _: Option<FileSearchOptions<'a>>,
+    ) -> BoxFuture<'a, Result<Vec<FileObject>, Error>> {
+        Box::pin(async move {
+            let prefix = path.map(|path| format!("{path}/")).unwrap_or_default();
+            let files = self
+                .objects
+                .lock()
+                .unwrap()
+                .keys()
+                .filter(|(bucket, _)| bucket == bucket_id)
+                .filter_map(|(_, key)| key.strip_prefix(&prefix))
+                .map(|name| FileObject {
+                    name: name.to_string(),
+                    id: Some(name.to_string()),
+                    updated_at: None,
+                    created_at: None,
+                    last_accessed_at: None,
+                    metadata: None,
+                    user_metadata: None,
+                    bucket_id: Some(bucket_id.to_string()),
+                    owner: None,
+                    buckets: None,
+                })
+                .collect();
+            Ok(files)
+        })
+    }
+
+    fn exists<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: &'a str,
+    ) -> BoxFuture<'a, Result<bool, Error>> {
+        Box::pin(async move {
+            Ok(self
+                .objects
+                .lock()
+                .unwrap()
+                .contains_key(&(bucket_id.to_string(), path.to_string())))
+        })
+    }
+
+    fn delete_file<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: &'a str,
+    ) -> BoxFuture<'a, Result<BucketResponse, Error>> {
+        Box::pin(async move {
+            self.objects
+                .lock()
+                .unwrap()
+                .remove(&(bucket_id.to_string(), path.to_string()))
+                .map(|_| BucketResponse {
+                    message: "Successfully deleted".to_string(),
+                })
+                .ok_or_else(|| Self::not_found(bucket_id, path))
+        })
+    }
+
+    fn delete_files<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        paths: Vec<&'a str>,
+    ) -> BoxFuture<'a, Result<Vec<FileObject>, Error>> {
+        Box::pin(async move {
+            let mut objects = self.objects.lock().unwrap();
+            paths.iter().for_each(|path| {
+                objects.remove(&(bucket_id.to_string(), path.to_string()));
+            });
+            Ok(Vec::new())
+        })
+    }
+
+    fn copy_file<'a>(
+        &'a self,
+        from_bucket: &'a str,
+        to_bucket: Option<&'a str>,
+        from_path: &'a str,
+        to_path: Option<&'a str>,
+        _: bool,
+    ) -> BoxFuture<'a, Result<String, Error>> {
+        Box::pin(async move {
+            let data = self.download_file(from_bucket, from_path, None).await?;
+            let to_bucket = to_bucket.unwrap_or(from_bucket);
+            let to_path = to_path.unwrap_or(from_path);
+            self.put(to_bucket, to_path, data);
+            Ok(format!("{to_bucket}/{to_path}"))
+        })
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        from_bucket: &'a str,
+        to_bucket: Option<&'a str>,
+        from_path: &'a str,
+        to_path: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Outcome, Error>> {
+        Box::pin(async move {
+            self.copy_file(from_bucket, to_bucket, from_path, to_path, true)
+                .await?;
+            self.delete_file(from_bucket, from_path).await?;
+            Ok(Outcome::Success { message: None })
+        })
+    }
+
+    fn create_signed_url<'a>(
+        &'a self,
+        bucket_id: &'a str,
+        path: &'a str,
+        _: u64,
+        _: Option<DownloadOptions<'a>>,
+    ) -> BoxFuture<'a, Result<String, Error>> {
+        Box::pin(async move { Ok(format!("memory://{bucket_id}/{path}?token=fake")) })
+    }
+}
+
+/// Downstream code written against the trait, rather than `StorageClient`
+async fn archive_report(storage: &dyn StorageBackend, name: &str) -> Result<String, Error> {
+    let draft = format!("drafts/{name}");
+    let archived = format!("archive/{name}");
+
+    storage
+        .move_file("reports", None, &draft, Some(&archived))
+        .await?;
+    storage
+        .create_signed_url("reports", &archived, 3600, None)
+        .await
+}
+
+#[tokio::test]
+async fn test_fake_storage_backend() {
+    let storage = FakeStorage::default();
+
+    storage
+        .upload_file("reports", b"q3".to_vec(), "drafts/q3.txt", None)
+        .await
+        .unwrap();
+
+    let url = archive_report(&storage, "q3.txt").await.unwrap();
+    assert_eq!(url, "memory://reports/archive/q3.txt?token=fake");
+
+    assert!(!storage.exists("reports", "drafts/q3.txt").await.unwrap());
+    assert_eq!(
+        storage
+            .download_file("reports", "archive/q3.txt", None)
+            .await
+            .unwrap(),
+        b"q3"
+    );
+
+    let names: Vec<_> = storage
+        .list_files("reports", Some("archive"), None)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|file| file.name)
+        .collect();
+    assert_eq!(names, vec!["q3.txt"]);
+
+    assert!(matches!(
+        archive_report(&storage, "missing.txt").await,
+        Err(Error::NotFound { .. })
+    ));
+}
+
+#[test]
+fn test_storage_client_is_a_storage_backend() {
+    let client = StorageClient::new("https://example.supabase.co".to_string(), "key".to_string());
+    let _: &dyn StorageBackend = &client;
+}
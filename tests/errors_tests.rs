@@ -0,0 +1,89 @@
+use std::error::Error as _;
+
+use reqwest::header::HeaderValue;
+use supabase_storage_rs::errors::Error;
+
+#[test]
+fn test_serde_error_chains_source() {
+    let inner = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+    let expected = inner.to_string();
+    let error = Error::from(inner);
+
+    let source = error.source().expect("SerdeError should expose its source");
+    assert!(source.is::<serde_json::Error>());
+    assert_eq!(source.to_string(), expected);
+}
+
+#[test]
+fn test_invalid_header_value_chains_source() {
+    let inner = HeaderValue::from_str("bad\nvalue").unwrap_err();
+    let error = Error::from(inner);
+
+    let source = error
+        .source()
+        .expect("InvalidHeaderValue should expose its source");
+    assert!(source.is::<reqwest::header::InvalidHeaderValue>());
+}
+
+#[test]
+fn test_request_error_chains_source() {
+    let inner = reqwest::Client::new().get("not a url").build().unwrap_err();
+    let expected = inner.to_string();
+    let error = Error::from(inner);
+
+    let source = error
+        .source()
+        .expect("RequestError should expose its source");
+    assert!(source.is::<reqwest::Error>());
+    assert_eq!(source.to_string(), expected);
+}
+
+#[test]
+fn test_invalid_environment_variable_chains_source() {
+    let error = Error::from(std::env::VarError::NotPresent);
+
+    let source = error
+        .source()
+        .expect("InvalidEnvironmentVariable should expose its source");
+    assert!(source.is::<std::env::VarError>());
+}
+
+#[test]
+fn test_message_variants_have_no_source() {
+    let error = Error::Unauthorized {
+        message: "jwt expired".to_string(),
+    };
+
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn test_into_io_error() {
+    let not_found = std::io::Error::from(Error::NotFound {
+        message: "Object not found: bucket/file.txt".to_string(),
+    });
+    assert_eq!(not_found.kind(), std::io::ErrorKind::NotFound);
+    assert!(matches!(
+        not_found.into_inner().unwrap().downcast_ref::<Error>(),
+        Some(Error::NotFound { .. })
+    ));
+
+    let validation = std::io::Error::from(Error::Validation {
+        field_errors: vec![("public".to_string(), "must be boolean".to_string())],
+    });
+    assert_eq!(validation.kind(), std::io::ErrorKind::InvalidInput);
+
+    let parse_error = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+    let other = std::io::Error::from(Error::from(parse_error));
+    assert_eq!(other.kind(), std::io::ErrorKind::Other);
+}
+
+#[test]
+fn test_middleware_error_defined_without_feature() {
+    // Matching on the variant compiles whether or not the `middleware` feature is enabled
+    let error = Error::MiddlewareError {
+        message: "rate limited".to_string(),
+    };
+
+    assert!(matches!(error, Error::MiddlewareError { .. }));
+}
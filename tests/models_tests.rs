@@ -0,0 +1,545 @@
+use std::{collections::HashSet, time::Duration};
+
+use supabase_storage_rs::models::{
+    sort_file_objects, Bucket, Column, FileObject, FileOptions, Metadata, MimeType, Order, SortBy,
+};
+
+#[test]
+fn test_bucket_empty_owner_is_none() {
+    let bucket: Bucket = serde_json::from_value(serde_json::json!({
+        "id": "avatars",
+        "name": "avatars",
+        "owner": "",
+        "public": false,
+        "created_at": "2024-11-25T10:00:00.000Z",
+        "updated_at": "2024-11-25T10:00:00.000Z"
+    }))
+    .unwrap();
+
+    assert_eq!(bucket.owner, None);
+}
+
+#[test]
+fn test_bucket_owner_is_some() {
+    let bucket: Bucket = serde_json::from_value(serde_json::json!({
+        "id": "avatars",
+        "name": "avatars",
+        "owner": "0f7ab7b6-0b0e-4a53-a4c5-3b4c2e0e8f5f",
+        "public": false,
+        "created_at": "2024-11-25T10:00:00.000Z",
+        "updated_at": "2024-11-25T10:00:00.000Z"
+    }))
+    .unwrap();
+
+    assert_eq!(
+        bucket.owner.as_deref(),
+        Some("0f7ab7b6-0b0e-4a53-a4c5-3b4c2e0e8f5f")
+    );
+}
+
+#[test]
+fn test_bucket_audit_fields() {
+    let bucket: Bucket = serde_json::from_value(serde_json::json!({
+        "id": "avatars",
+        "name": "avatars",
+        "public": false,
+        "created_at": "2024-11-25T10:00:00.000Z",
+        "updated_at": "2024-11-25T10:00:00.000Z",
+        "created_by": "0f7ab7b6-0b0e-4a53-a4c5-3b4c2e0e8f5f",
+        "updated_by": "7c9e6679-7425-40de-944b-e07fc1f90ae7"
+    }))
+    .unwrap();
+
+    assert_eq!(
+        bucket.created_by.as_deref(),
+        Some("0f7ab7b6-0b0e-4a53-a4c5-3b4c2e0e8f5f")
+    );
+    assert_eq!(
+        bucket.updated_by.as_deref(),
+        Some("7c9e6679-7425-40de-944b-e07fc1f90ae7")
+    );
+
+    let older: Bucket = serde_json::from_value(serde_json::json!({
+        "id": "avatars",
+        "name": "avatars",
+        "public": false,
+        "created_at": "2024-11-25T10:00:00.000Z",
+        "updated_at": "2024-11-25T10:00:00.000Z"
+    }))
+    .unwrap();
+
+    assert_eq!(older.created_by, None);
+    assert_eq!(older.updated_by, None);
+}
+
+fn file_object(name: &str, updated_at: Option<&str>) -> FileObject {
+    serde_json::from_value(serde_json::json!({
+        "name": name,
+        "updated_at": updated_at,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_bucket_file_size_limit_as_string_or_number() {
+    let bucket_json = |file_size_limit: serde_json::Value| {
+        serde_json::json!({
+            "id": "avatars",
+            "name": "avatars",
+            "owner": "",
+            "public": false,
+            "file_size_limit": file_size_limit,
+            "created_at": "2024-11-25T10:00:00.000Z",
+            "updated_at": "2024-11-25T10:00:00.000Z"
+        })
+    };
+
+    let from_number: Bucket = serde_json::from_value(bucket_json(1000000.into())).unwrap();
+    let from_string: Bucket = serde_json::from_value(bucket_json("1000000".into())).unwrap();
+    let from_null: Bucket = serde_json::from_value(bucket_json(serde_json::Value::Null)).unwrap();
+
+    assert_eq!(from_number.file_size_limit, Some(1000000));
+    assert_eq!(from_string.file_size_limit, Some(1000000));
+    assert_eq!(from_null.file_size_limit, None);
+}
+
+#[test]
+fn test_bucket_file_size_limit_rejects_non_numeric_string() {
+    let result = serde_json::from_value::<Bucket>(serde_json::json!({
+        "id": "avatars",
+        "name": "avatars",
+        "public": false,
+        "file_size_limit": "unlimited",
+        "created_at": "2024-11-25T10:00:00.000Z",
+        "updated_at": "2024-11-25T10:00:00.000Z"
+    }));
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_sort_file_objects_by_updated_at_desc() {
+    let mut files = vec![
+        file_object("old.txt", Some("2024-01-01T00:00:00.000Z")),
+        file_object("folder", None),
+        file_object("new.txt", Some("2024-12-01T00:00:00.000Z")),
+        file_object("middle.txt", Some("2024-06-01T00:00:00.000Z")),
+    ];
+
+    sort_file_objects(
+        &mut files,
+        &SortBy {
+            column: Column::UpdatedAt,
+            order: Order::Desc,
+        },
+    );
+
+    let names: Vec<&str> = files.iter().map(|file| file.name.as_str()).collect();
+    assert_eq!(names, vec!["new.txt", "middle.txt", "old.txt", "folder"]);
+}
+
+#[test]
+fn test_sort_file_objects_by_size() {
+    let sized = |name: &str, size: i64| -> FileObject {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "id": "0f7ab7b6-0b0e-4a53-a4c5-3b4c2e0e8f5f",
+            "metadata": { "size": size, "mimetype": "text/plain" }
+        }))
+        .unwrap()
+    };
+
+    let mut files = vec![
+        sized("medium.txt", 2048),
+        file_object("folder", None),
+        sized("large.txt", 4096),
+        sized("small.txt", 12),
+    ];
+
+    sort_file_objects(
+        &mut files,
+        &SortBy {
+            column: Column::Size,
+            order: Order::Asc,
+        },
+    );
+
+    let names: Vec<&str> = files.iter().map(|file| file.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["small.txt", "medium.txt", "large.txt", "folder"]
+    );
+}
+
+#[test]
+fn test_sort_by_serializes_api_values() {
+    let sort_by = SortBy {
+        column: Column::UpdatedAt,
+        order: Order::Desc,
+    };
+
+    assert_eq!(
+        serde_json::to_string(&sort_by).unwrap(),
+        r#"{"column":"updated_at","order":"desc"}"#
+    );
+
+    let columns = [
+        (Column::Name, "name"),
+        (Column::ID, "id"),
+        (Column::UpdatedAt, "updated_at"),
+        (Column::CreatedAt, "created_at"),
+        (Column::LastAccessedAt, "last_accessed_at"),
+    ];
+
+    for (column, expected) in columns {
+        assert_eq!(serde_json::to_value(column).unwrap(), expected);
+        assert_eq!(
+            serde_json::from_value::<Column>(expected.into()).unwrap(),
+            column
+        );
+    }
+
+    assert_eq!(serde_json::to_value(Order::Asc).unwrap(), "asc");
+    assert_eq!(serde_json::to_value(Order::Desc).unwrap(), "desc");
+}
+
+#[test]
+fn test_cache_control_from_str() {
+    assert_eq!(
+        FileOptions::cache_control_from_str("max-age=3600"),
+        Some(Duration::from_secs(3600))
+    );
+    assert_eq!(
+        FileOptions::cache_control_from_str("public, Max-Age = 60, immutable"),
+        Some(Duration::from_secs(60))
+    );
+    assert_eq!(FileOptions::cache_control_from_str("no-store"), None);
+}
+
+#[test]
+fn test_file_object_size_and_mime_type() {
+    let file: FileObject = serde_json::from_value(serde_json::json!({
+        "name": "aaa.jpg",
+        "id": "0f7ab7b6-0b0e-4a53-a4c5-3b4c2e0e8f5f",
+        "metadata": {
+            "eTag": "\"c4ca4238a0b923820dcc509a6f75849b\"",
+            "size": 2048,
+            "mimetype": "image/jpeg",
+            "cacheControl": "max-age=3600",
+            "lastModified": "2024-11-25T10:00:00.000Z",
+            "contentLength": 2048,
+            "httpStatusCode": 200
+        }
+    }))
+    .unwrap();
+
+    assert_eq!(file.size(), Some(2048));
+    assert_eq!(file.mime_type(), Some("image/jpeg"));
+}
+
+#[test]
+fn test_folder_size_and_mime_type() {
+    let folder = file_object("folder", None);
+
+    assert!(folder.is_folder());
+    assert_eq!(folder.size(), None);
+    assert_eq!(folder.mime_type(), None);
+}
+
+#[test]
+fn test_full_path_joins_prefix() {
+    let file = file_object("aaa.jpg", None);
+
+    assert_eq!(file.full_path(""), "aaa.jpg");
+    assert_eq!(file.full_path("folder"), "folder/aaa.jpg");
+    assert_eq!(file.full_path("/folder/nested/"), "folder/nested/aaa.jpg");
+}
+
+#[test]
+fn test_partial_metadata() {
+    let metadata: Metadata = serde_json::from_value(serde_json::json!({
+        "size": 12,
+        "mimetype": "text/plain",
+        "lastModified": "2024-11-25T10:00:00.000Z"
+    }))
+    .unwrap();
+
+    assert_eq!(metadata.size, 12);
+    assert_eq!(metadata.mimetype, "text/plain");
+    assert_eq!(
+        metadata.last_modified.as_deref(),
+        Some("2024-11-25T10:00:00.000Z")
+    );
+    assert_eq!(metadata.etag, None);
+    assert_eq!(metadata.cache_control, None);
+    assert_eq!(metadata.content_length, None);
+    assert_eq!(metadata.http_status_code, None);
+}
+
+#[test]
+fn test_file_object_null_metadata() {
+    let file: FileObject = serde_json::from_value(serde_json::json!({
+        "name": "aaa.jpg",
+        "id": "0f7ab7b6-0b0e-4a53-a4c5-3b4c2e0e8f5f",
+        "metadata": null
+    }))
+    .unwrap();
+
+    assert!(file.metadata.is_none());
+    assert_eq!(file.size(), None);
+}
+
+#[test]
+fn test_metadata_size_above_i32_max() {
+    let size = i64::from(i32::MAX) + 1024;
+
+    let metadata: Metadata = serde_json::from_value(serde_json::json!({
+        "eTag": "\"c4ca4238a0b923820dcc509a6f75849b\"",
+        "size": size,
+        "mimetype": "video/mp4",
+        "cacheControl": "max-age=3600",
+        "lastModified": "2024-11-25T10:00:00.000Z",
+        "contentLength": size,
+        "httpStatusCode": 200
+    }))
+    .unwrap();
+
+    assert_eq!(metadata.size, size);
+    assert_eq!(metadata.content_length, Some(size));
+}
+
+#[test]
+fn test_mime_type_set_membership() {
+    let allowed: HashSet<MimeType> = [MimeType::PNG, MimeType::JPEG, MimeType::Custom("image/*")]
+        .into_iter()
+        .collect();
+
+    assert!(allowed.contains(&MimeType::PNG));
+    assert!(allowed.contains(&MimeType::Custom("image/*")));
+    assert!(!allowed.contains(&MimeType::PDF));
+    assert!(!allowed.contains(&MimeType::Custom("video/*")));
+    assert_eq!(allowed.len(), 3);
+}
+
+#[test]
+fn test_mime_type_from_strings() {
+    assert_eq!(MimeType::from("image/png"), MimeType::PNG);
+    assert_eq!(MimeType::from(" Application/PDF "), MimeType::PDF);
+    assert_eq!(MimeType::from("image/*"), MimeType::Custom("image/*"));
+
+    assert_eq!(MimeType::from("video/mp4".to_string()), MimeType::MP4);
+    let custom = MimeType::from("application/x-custom".to_string());
+    assert_eq!(
+        custom,
+        MimeType::CustomOwned("application/x-custom".to_string())
+    );
+    assert_eq!(custom.as_str(), "application/x-custom");
+}
+
+#[test]
+fn test_custom_mime_type_equality() {
+    let borrowed = MimeType::from("application/x-custom");
+    let owned = MimeType::from("application/x-custom".to_string());
+    assert_eq!(borrowed, owned);
+
+    let set: HashSet<MimeType> = [borrowed, owned].into_iter().collect();
+    assert_eq!(set.len(), 1);
+    assert!(set.contains(&MimeType::Custom("application/x-custom")));
+    assert_ne!(
+        MimeType::from("application/x-custom"),
+        MimeType::from("x/other")
+    );
+}
+
+fn bucket_allowing(allowed_mime_types: serde_json::Value) -> Bucket {
+    serde_json::from_value(serde_json::json!({
+        "id": "avatars",
+        "name": "avatars",
+        "public": false,
+        "allowed_mime_types": allowed_mime_types,
+        "created_at": "2024-11-25T10:00:00.000Z",
+        "updated_at": "2024-11-25T10:00:00.000Z"
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_bucket_allows_listed_mime_type() {
+    let bucket = bucket_allowing(serde_json::json!(["image/png", "application/pdf"]));
+
+    assert!(bucket.allows_mime_type("image/png"));
+    assert!(bucket.allows_mime_type("Application/PDF"));
+}
+
+#[test]
+fn test_bucket_rejects_unlisted_mime_type() {
+    let bucket = bucket_allowing(serde_json::json!(["image/png"]));
+
+    assert!(!bucket.allows_mime_type("image/jpeg"));
+    assert!(!bucket.allows_mime_type("text/plain;charset=UTF-8"));
+}
+
+#[test]
+fn test_bucket_allows_wildcard_mime_type() {
+    let bucket = bucket_allowing(serde_json::json!(["image/*", "text/plain"]));
+
+    assert!(bucket.allows_mime_type("image/webp"));
+    assert!(bucket.allows_mime_type("text/plain;charset=UTF-8"));
+    assert!(!bucket.allows_mime_type("video/mp4"));
+
+    assert!(bucket_allowing(serde_json::json!(["*/*"])).allows_mime_type("video/mp4"));
+    assert!(bucket_allowing(serde_json::Value::Null).allows_mime_type("video/mp4"));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_chrono_timestamps() {
+    use chrono::{TimeZone, Utc};
+    use supabase_storage_rs::models::DownloadResponse;
+
+    let bucket = bucket_allowing(serde_json::Value::Null);
+    let expected = Utc.with_ymd_and_hms(2024, 11, 25, 10, 0, 0).unwrap();
+
+    assert_eq!(bucket.created_at_datetime().unwrap(), expected);
+    assert_eq!(bucket.updated_at_datetime().unwrap(), expected);
+
+    let file = file_object("aaa.jpg", Some("2024-11-25T12:00:00.000+02:00"));
+    assert_eq!(file.updated_at_datetime().unwrap(), Some(expected));
+    assert_eq!(file.created_at_datetime().unwrap(), None);
+
+    let invalid = file_object("aaa.jpg", Some("yesterday"));
+    assert!(invalid.updated_at_datetime().is_err());
+
+    let download = DownloadResponse {
+        last_modified: Some("Mon, 25 Nov 2024 10:00:00 GMT".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(download.last_modified_datetime().unwrap(), Some(expected));
+    assert_eq!(
+        DownloadResponse::default()
+            .last_modified_datetime()
+            .unwrap(),
+        None
+    );
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn test_time_timestamps() {
+    use supabase_storage_rs::models::DownloadResponse;
+    use time::OffsetDateTime;
+
+    let bucket = bucket_allowing(serde_json::Value::Null);
+    // 2024-11-25T10:00:00Z
+    let expected = OffsetDateTime::from_unix_timestamp(1_732_528_800).unwrap();
+
+    assert_eq!(bucket.created_at_offset_datetime().unwrap(), expected);
+    assert_eq!(bucket.updated_at_offset_datetime().unwrap(), expected);
+
+    let file = file_object("aaa.jpg", Some("2024-11-25T12:00:00.000+02:00"));
+    assert_eq!(file.updated_at_offset_datetime().unwrap(), Some(expected));
+    assert_eq!(file.created_at_offset_datetime().unwrap(), None);
+
+    let invalid = file_object("aaa.jpg", Some("yesterday"));
+    assert!(invalid.updated_at_offset_datetime().is_err());
+
+    let download = DownloadResponse {
+        last_modified: Some("Mon, 25 Nov 2024 10:00:00 GMT".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        download.last_modified_offset_datetime().unwrap(),
+        Some(expected)
+    );
+}
+
+#[test]
+fn test_bucket_serde_round_trip() {
+    let json = serde_json::json!({
+        "id": "avatars",
+        "name": "avatars",
+        "owner": "0f7ab7b6-0b0e-4a53-a4c5-3b4c2e0e8f5f",
+        "public": true,
+        "file_size_limit": 1000000,
+        "allowed_mime_types": ["image/png", "image/jpeg"],
+        "created_at": "2024-11-25T10:00:00.000Z",
+        "updated_at": "2024-11-25T10:00:00.000Z"
+    });
+
+    let bucket: Bucket = serde_json::from_value(json.clone()).unwrap();
+    let serialized = serde_json::to_value(&bucket).unwrap();
+
+    assert_eq!(serialized, json);
+    assert_eq!(
+        serde_json::from_value::<Bucket>(serialized).unwrap(),
+        bucket
+    );
+}
+
+#[test]
+fn test_file_object_serde_round_trip() {
+    let json = serde_json::json!({
+        "name": "aaa.jpg",
+        "id": "0f7ab7b6-0b0e-4a53-a4c5-3b4c2e0e8f5f",
+        "updated_at": "2024-11-25T10:00:00.000Z",
+        "created_at": "2024-11-25T10:00:00.000Z",
+        "last_accessed_at": "2024-11-25T10:00:00.000Z",
+        "metadata": {
+            "eTag": "\"c4ca4238a0b923820dcc509a6f75849b\"",
+            "size": 2048,
+            "mimetype": "image/jpeg",
+            "cacheControl": "max-age=3600",
+            "lastModified": "2024-11-25T10:00:00.000Z",
+            "contentLength": 2048,
+            "httpStatusCode": 200
+        },
+        "user_metadata": { "source": "camera" },
+        "bucket_id": "avatars",
+        "owner": "0f7ab7b6-0b0e-4a53-a4c5-3b4c2e0e8f5f"
+    });
+
+    let file: FileObject = serde_json::from_value(json.clone()).unwrap();
+    let serialized = serde_json::to_value(&file).unwrap();
+
+    assert_eq!(serialized, json);
+    assert_eq!(
+        serde_json::from_value::<FileObject>(serialized).unwrap(),
+        file
+    );
+}
+
+#[test]
+fn test_metadata_byte_len_prefers_content_length() {
+    let metadata: Metadata = serde_json::from_value(serde_json::json!({
+        "size": 12,
+        "mimetype": "text/plain",
+        "contentLength": 16
+    }))
+    .unwrap();
+
+    assert_eq!(metadata.byte_len(), 16);
+
+    let without_content_length = Metadata {
+        content_length: None,
+        ..metadata
+    };
+    assert_eq!(without_content_length.byte_len(), 12);
+}
+
+#[test]
+fn test_metadata_serde_round_trip() {
+    let json = serde_json::json!({
+        "size": 12,
+        "mimetype": "text/plain",
+        "lastModified": "2024-11-25T10:00:00.000Z"
+    });
+
+    let metadata: Metadata = serde_json::from_value(json.clone()).unwrap();
+    let serialized = serde_json::to_value(&metadata).unwrap();
+
+    assert_eq!(serialized, json);
+    assert_eq!(
+        serde_json::from_value::<Metadata>(serialized).unwrap(),
+        metadata
+    );
+}
@@ -0,0 +1,35 @@
+use supabase_storage_rs::prelude::*;
+
+#[test]
+fn test_prelude_constructs_client() {
+    let client = StorageClient::new(
+        "https://project.supabase.co".to_string(),
+        "test-api-key".to_string(),
+    );
+
+    let options = FileSearchOptions {
+        sort_by: Some(SortBy {
+            column: Column::Name,
+            order: Order::Asc,
+        }),
+        ..Default::default()
+    };
+
+    let upload = FileOptions {
+        content_type: Some(MimeType::PNG.as_str()),
+        ..Default::default()
+    };
+
+    let download = DownloadOptions {
+        transform: None::<TransformOptions>,
+        download: Some(true),
+    };
+
+    let error: Option<Error> = None;
+
+    assert_eq!(client.project_url, "https://project.supabase.co");
+    assert!(options.sort_by.is_some());
+    assert_eq!(upload.content_type, Some("image/png"));
+    assert_eq!(download.download, Some(true));
+    assert!(error.is_none());
+}
@@ -0,0 +1,55 @@
+//! Pins the public API's exposure to `reqwest`, so a new leak fails to compile here first
+
+use std::{future::Future, marker::PhantomData};
+
+use bytes::Bytes;
+use supabase_storage_rs::models::{
+    ConditionalDownload, DownloadResponse, Outcome, RequestMiddleware, StorageClientBuilder,
+};
+use supabase_storage_rs::prelude::*;
+use supabase_storage_rs::reqwest;
+
+/// The output type of a future, checked at compile time without polling it
+fn output_of<F: Future>(_: F) -> PhantomData<F::Output> {
+    PhantomData
+}
+
+#[test]
+fn test_responses_are_crate_owned() {
+    let client = StorageClient::new("https://example.supabase.co".to_string(), "key".to_string());
+
+    let _: PhantomData<Result<DownloadResponse, Error>> =
+        output_of(client.download_file_response("bucket", "path", None));
+    let _: PhantomData<Result<ConditionalDownload, Error>> =
+        output_of(client.download_file_if_modified("bucket", "path", "\"etag\"", None));
+    let _: PhantomData<Result<Bytes, Error>> =
+        output_of(client.download_file_bytes("bucket", "path", None));
+    let _: PhantomData<Result<Vec<u8>, Error>> =
+        output_of(client.download_file("bucket", "path", None));
+    let _: PhantomData<Result<Outcome, Error>> = output_of(client.empty_bucket("bucket"));
+}
+
+#[test]
+fn test_intended_reqwest_surface() {
+    let client = StorageClient::new("https://example.supabase.co".to_string(), "key".to_string());
+
+    let _: &reqwest::Client = &client.client;
+    let _: fn(StorageClientBuilder, reqwest::Client) -> StorageClientBuilder =
+        StorageClientBuilder::http_client;
+    let _: PhantomData<Result<reqwest::Url, Error>> =
+        output_of(client.public_url_parsed("bucket", "path", None));
+
+    let _: RequestMiddleware = std::sync::Arc::new(
+        |request: reqwest::RequestBuilder| -> reqwest::RequestBuilder { request },
+    );
+
+    let _ = |error: Error| match error {
+        Error::StorageError { status, method, .. } => {
+            let _: (reqwest::StatusCode, reqwest::Method) = (status, method);
+        }
+        Error::RequestError(err) => {
+            let _: reqwest::Error = err;
+        }
+        _ => {}
+    };
+}
@@ -0,0 +1,223 @@
+#![cfg(feature = "testing")]
+
+use supabase_storage_rs::prelude::*;
+use supabase_storage_rs::testing::MemoryStorage;
+
+#[tokio::test]
+async fn test_memory_storage_upload_and_download() {
+    let storage = MemoryStorage::new().with_bucket("avatars");
+
+    storage
+        .upload_file("avatars", b"first".to_vec(), "user.png", None)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        storage
+            .download_file("avatars", "user.png", None)
+            .await
+            .unwrap(),
+        b"first"
+    );
+    assert!(storage.exists("avatars", "user.png").await.unwrap());
+    assert!(!storage.exists("avatars", "other.png").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_memory_storage_upsert() {
+    let storage = MemoryStorage::new().with_bucket("avatars");
+
+    storage
+        .upload_file("avatars", b"first".to_vec(), "user.png", None)
+        .await
+        .unwrap();
+
+    let duplicate = storage
+        .upload_file("avatars", b"second".to_vec(), "user.png", None)
+        .await;
+    assert!(matches!(duplicate, Err(Error::AlreadyExists { name }) if name == "avatars/user.png"));
+    assert_eq!(storage.get("avatars", "user.png").unwrap(), b"first");
+
+    let options = FileOptions {
+        upsert: true,
+        ..Default::default()
+    };
+    storage
+        .upload_file("avatars", b"second".to_vec(), "user.png", Some(options))
+        .await
+        .unwrap();
+    assert_eq!(storage.get("avatars", "user.png").unwrap(), b"second");
+
+    storage
+        .update_file("avatars", b"third".to_vec(), "user.png", None)
+        .await
+        .unwrap();
+    assert_eq!(storage.get("avatars", "user.png").unwrap(), b"third");
+}
+
+#[tokio::test]
+async fn test_memory_storage_not_found() {
+    let storage = MemoryStorage::new().with_bucket("avatars");
+
+    assert!(matches!(
+        storage.download_file("avatars", "missing.png", None).await,
+        Err(Error::NotFound { .. })
+    ));
+    assert!(matches!(
+        storage
+            .update_file("avatars", b"data".to_vec(), "missing.png", None)
+            .await,
+        Err(Error::NotFound { .. })
+    ));
+    assert!(matches!(
+        storage.delete_file("avatars", "missing.png").await,
+        Err(Error::NotFound { .. })
+    ));
+    assert!(matches!(
+        storage
+            .copy_file("avatars", None, "missing.png", Some("copy.png"), false)
+            .await,
+        Err(Error::NotFound { .. })
+    ));
+    assert!(matches!(
+        storage
+            .upload_file("missing_bucket", b"data".to_vec(), "user.png", None)
+            .await,
+        Err(Error::NotFound { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_memory_storage_lists_by_prefix() {
+    let storage = MemoryStorage::new()
+        .with_bucket("photos")
+        .with_bucket("other");
+
+    for path in [
+        "2024/beach.jpg",
+        "2024/city.jpg",
+        "2024/summer/lake.jpg",
+        "2025/snow.jpg",
+        "root.jpg",
+    ] {
+        storage
+            .upload_file("photos", path.as_bytes().to_vec(), path, None)
+            .await
+            .unwrap();
+    }
+    storage
+        .upload_file("other", b"data".to_vec(), "2024/other.jpg", None)
+        .await
+        .unwrap();
+
+    let listing = storage
+        .list_files("photos", Some("2024/"), None)
+        .await
+        .unwrap();
+    let names: Vec<_> = listing.iter().map(|file| file.name.as_str()).collect();
+    assert_eq!(names, vec!["summer", "beach.jpg", "city.jpg"]);
+    assert!(listing[0].is_folder());
+    assert_eq!(listing[1].size(), Some("2024/beach.jpg".len() as i64));
+
+    let root = storage.list_files("photos", None, None).await.unwrap();
+    let names: Vec<_> = root.iter().map(|file| file.name.as_str()).collect();
+    assert_eq!(names, vec!["2024", "2025", "root.jpg"]);
+
+    let options = FileSearchOptions {
+        search: Some("city"),
+        ..Default::default()
+    };
+    let searched = storage
+        .list_files("photos", Some("2024"), Some(options))
+        .await
+        .unwrap();
+    assert_eq!(searched.len(), 1);
+    assert_eq!(searched[0].name, "city.jpg");
+
+    let options = FileSearchOptions {
+        sort_by: Some(SortBy {
+            column: Column::Name,
+            order: Order::Desc,
+        }),
+        ..Default::default()
+    };
+    let sorted = storage
+        .list_files("photos", Some("2024"), Some(options))
+        .await
+        .unwrap();
+    let names: Vec<_> = sorted.iter().map(|file| file.name.as_str()).collect();
+    assert_eq!(names, vec!["summer", "city.jpg", "beach.jpg"]);
+
+    let options = FileSearchOptions {
+        sort_by: Some(SortBy {
+            column: Column::Size,
+            order: Order::Asc,
+        }),
+        ..Default::default()
+    };
+    assert!(matches!(
+        storage
+            .list_files("photos", Some("2024"), Some(options))
+            .await,
+        Err(Error::InvalidArgument { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_memory_storage_copy_move_and_delete() {
+    let storage = MemoryStorage::new()
+        .with_bucket("docs")
+        .with_bucket("archive");
+
+    storage
+        .upload_file("docs", b"report".to_vec(), "report.txt", None)
+        .await
+        .unwrap();
+
+    let key = storage
+        .copy_file("docs", Some("archive"), "report.txt", None, true)
+        .await
+        .unwrap();
+    assert_eq!(key, "archive/report.txt");
+    assert_eq!(storage.get("archive", "report.txt").unwrap(), b"report");
+
+    storage
+        .move_file("docs", None, "report.txt", Some("old/report.txt"))
+        .await
+        .unwrap();
+    assert_eq!(storage.get("docs", "report.txt"), None);
+    assert_eq!(storage.get("docs", "old/report.txt").unwrap(), b"report");
+
+    // Neither copying nor moving replaces an existing destination
+    storage
+        .upload_file("docs", b"draft".to_vec(), "draft.txt", None)
+        .await
+        .unwrap();
+    assert!(matches!(
+        storage
+            .copy_file("docs", None, "draft.txt", Some("old/report.txt"), false)
+            .await,
+        Err(Error::AlreadyExists { name }) if name == "docs/old/report.txt"
+    ));
+    assert!(matches!(
+        storage
+            .move_file("docs", None, "draft.txt", Some("old/report.txt"))
+            .await,
+        Err(Error::AlreadyExists { .. })
+    ));
+    assert_eq!(storage.get("docs", "old/report.txt").unwrap(), b"report");
+    assert_eq!(storage.get("docs", "draft.txt").unwrap(), b"draft");
+
+    let deleted = storage
+        .delete_files("docs", vec!["old/report.txt", "missing.txt"])
+        .await
+        .unwrap();
+    assert_eq!(deleted.len(), 1);
+    assert_eq!(deleted[0].name, "old/report.txt");
+
+    assert!(storage.delete_bucket("archive"));
+    assert!(matches!(
+        storage.download_file("archive", "report.txt", None).await,
+        Err(Error::NotFound { .. })
+    ));
+}